@@ -0,0 +1,266 @@
+use crate::jsonpath;
+use crate::response::ParsedResponse;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Checks evaluated against a response after a saved request runs.
+///
+/// ```json
+/// "assert": {
+///   "status":  { "min": 200, "max": 299 },
+///   "headers": { "Content-Type": { "matches": "^application/json" } },
+///   "body":    [ { "path": "$.id", "type": "number" } ],
+///   "max_time_ms": 500
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Assertions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status:         Option<StatusAssertion>,
+    /// Header name (case-insensitive) → check.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers:        BTreeMap<String, HeaderAssertion>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub body:           Vec<BodyAssertion>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_time_ms:    Option<u128>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_size_bytes: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_bytes: Option<usize>,
+}
+
+/// Either an exact status code or an inclusive range.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StatusAssertion {
+    Equals(u16),
+    Range { min: u16, max: u16 },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HeaderAssertion {
+    /// `true` requires the header, `false` forbids it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub present: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equals:  Option<String>,
+    /// Regular expression the header value must match.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matches: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodyAssertion {
+    /// JSON pointer or JSONPath into the response body.
+    pub path:     String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equals:   Option<Value>,
+    /// Substring for strings, element for arrays, key for objects.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contains: Option<Value>,
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind:     Option<JsonType>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonType {
+    String,
+    Number,
+    Boolean,
+    Null,
+    Array,
+    Object,
+}
+
+impl std::fmt::Display for JsonType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonType::String  => write!(f, "string"),
+            JsonType::Number  => write!(f, "number"),
+            JsonType::Boolean => write!(f, "boolean"),
+            JsonType::Null    => write!(f, "null"),
+            JsonType::Array   => write!(f, "array"),
+            JsonType::Object  => write!(f, "object"),
+        }
+    }
+}
+
+impl JsonType {
+    pub fn of(value: &Value) -> Self {
+        match value {
+            Value::String(_) => JsonType::String,
+            Value::Number(_) => JsonType::Number,
+            Value::Bool(_)   => JsonType::Boolean,
+            Value::Null      => JsonType::Null,
+            Value::Array(_)  => JsonType::Array,
+            Value::Object(_) => JsonType::Object,
+        }
+    }
+}
+
+/// A single failed check, with what was expected and what was observed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AssertionFailure {
+    pub check:    String,
+    pub expected: String,
+    pub actual:   String,
+}
+
+impl std::fmt::Display for AssertionFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: expected {}, got {}", self.check, self.expected, self.actual)
+    }
+}
+
+impl Assertions {
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none()
+            && self.headers.is_empty()
+            && self.body.is_empty()
+            && self.max_time_ms.is_none()
+            && self.min_size_bytes.is_none()
+            && self.max_size_bytes.is_none()
+    }
+
+    /// Evaluate every check against `resp`. Without an explicit `status`
+    /// check, any status below 400 passes.
+    pub fn evaluate(&self, resp: &ParsedResponse) -> Vec<AssertionFailure> {
+        let mut failures = Vec::new();
+
+        match &self.status {
+            Some(StatusAssertion::Equals(code)) if resp.status != *code => {
+                failures.push(failure("status", code, resp.status));
+            }
+            Some(StatusAssertion::Range { min, max }) if !(*min..=*max).contains(&resp.status) => {
+                failures.push(failure("status", format!("{min}..={max}"), resp.status));
+            }
+            None if resp.status >= 400 => {
+                failures.push(failure("status", "< 400", resp.status));
+            }
+            _ => {}
+        }
+
+        for (name, check) in &self.headers {
+            check.evaluate(name, resp, &mut failures);
+        }
+
+        if !self.body.is_empty() {
+            match serde_json::from_str::<Value>(&resp.text()) {
+                Ok(json) => {
+                    for check in &self.body {
+                        check.evaluate(&json, &mut failures);
+                    }
+                }
+                Err(_) => failures.push(failure("body", "valid JSON", "unparseable body")),
+            }
+        }
+
+        if let Some(max) = self.max_time_ms {
+            if resp.elapsed_ms > max {
+                failures.push(failure("response time", format!("<= {max}ms"), format!("{}ms", resp.elapsed_ms)));
+            }
+        }
+        if let Some(min) = self.min_size_bytes {
+            if resp.size_bytes < min {
+                failures.push(failure("body size", format!(">= {min} bytes"), format!("{} bytes", resp.size_bytes)));
+            }
+        }
+        if let Some(max) = self.max_size_bytes {
+            if resp.size_bytes > max {
+                failures.push(failure("body size", format!("<= {max} bytes"), format!("{} bytes", resp.size_bytes)));
+            }
+        }
+
+        failures
+    }
+}
+
+impl HeaderAssertion {
+    fn evaluate(&self, name: &str, resp: &ParsedResponse, failures: &mut Vec<AssertionFailure>) {
+        let check = format!("header {name}");
+        let value = resp
+            .headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str());
+
+        let value = match (value, self.present) {
+            (Some(v), Some(false)) => {
+                failures.push(failure(&check, "absent", format!("{v:?}")));
+                return;
+            }
+            (Some(v), _)        => v,
+            (None, Some(false)) => return,
+            (None, _)           => {
+                failures.push(failure(&check, "present", "absent"));
+                return;
+            }
+        };
+
+        if let Some(expected) = &self.equals {
+            if value != expected {
+                failures.push(failure(&check, format!("{expected:?}"), format!("{value:?}")));
+            }
+        }
+        if let Some(pattern) = &self.matches {
+            match regex::Regex::new(pattern) {
+                Ok(re) if !re.is_match(value) => {
+                    failures.push(failure(&check, format!("match /{pattern}/"), format!("{value:?}")));
+                }
+                Err(e) => failures.push(failure(&check, format!("valid regex /{pattern}/"), e.to_string())),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl BodyAssertion {
+    fn evaluate(&self, json: &Value, failures: &mut Vec<AssertionFailure>) {
+        let check = format!("body {}", self.path);
+        let value = match jsonpath::select(json, &self.path) {
+            Ok(Some(v)) => v,
+            Ok(None) => {
+                failures.push(failure(&check, "a value", "no match"));
+                return;
+            }
+            Err(e) => {
+                failures.push(failure(&check, "a valid path", e.to_string()));
+                return;
+            }
+        };
+
+        if let Some(expected) = &self.equals {
+            if value != expected {
+                failures.push(failure(&check, expected, value));
+            }
+        }
+        if let Some(needle) = &self.contains {
+            let found = match (value, needle) {
+                (Value::String(s), Value::String(n))   => s.contains(n.as_str()),
+                (Value::Array(items), n)             => items.contains(n),
+                (Value::Object(map), Value::String(k)) => map.contains_key(k),
+                _                                    => false,
+            };
+            if !found {
+                failures.push(failure(&check, format!("to contain {needle}"), value));
+            }
+        }
+        if let Some(kind) = self.kind {
+            let actual = JsonType::of(value);
+            if actual != kind {
+                failures.push(failure(&check, format!("type {kind}"), format!("type {actual}")));
+            }
+        }
+    }
+}
+
+fn failure(check: &str, expected: impl std::fmt::Display, actual: impl std::fmt::Display) -> AssertionFailure {
+    AssertionFailure {
+        check:    check.to_string(),
+        expected: expected.to_string(),
+        actual:   actual.to_string(),
+    }
+}
@@ -0,0 +1,185 @@
+use crate::cli::Cli;
+use crate::error::{AppError, Result};
+use crate::oauth::{GrantType, OAuth2Config};
+use crate::signature::HmacConfig;
+use crate::sigv4::AwsSigV4Config;
+use serde::{Deserialize, Serialize};
+
+/// Placeholder written in place of secrets when a request is persisted.
+pub const REDACTED: &str = "********";
+
+/// Authentication applied by `HttpClient::send`, declared separately from
+/// raw headers so collections can describe it structurally.
+///
+/// ```json
+/// "auth": { "type": "basic", "username": "alice", "password": "{{password}}" }
+/// "auth": { "type": "bearer", "token": "{{token}}" }
+/// "auth": { "type": "digest", "username": "admin", "password": "{{password}}" }
+/// ```
+///
+/// See [`OAuth2Config`], [`AwsSigV4Config`] and [`HmacConfig`] for the
+/// `oauth2`, `aws-sigv4` and `hmac` types.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum Auth {
+    Basic {
+        username: String,
+        /// Prompted for without echo when omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        password: Option<String>,
+    },
+    Bearer {
+        token: String,
+    },
+    /// Answered by `HttpClient` once the server sends a Digest challenge.
+    Digest {
+        username: String,
+        /// Prompted for without echo when omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        password: Option<String>,
+    },
+    /// Resolved to a bearer token by `HttpClient` before sending.
+    OAuth2(OAuth2Config),
+    /// Signed by `HttpClient` once the request is fully built.
+    #[serde(rename = "aws-sigv4")]
+    AwsSigV4(AwsSigV4Config),
+    /// Signed by `HttpClient` over the final serialized body.
+    Hmac(HmacConfig),
+}
+
+impl Auth {
+    /// Build auth from the global `--auth` / `--bearer` flags, prompting for
+    /// a missing password.
+    pub fn from_cli(cli: &Cli) -> Result<Option<Self>> {
+        if let Some(spec) = &cli.aws_sigv4 {
+            return AwsSigV4Config::from_spec(spec).map(|cfg| Some(Auth::AwsSigV4(cfg)));
+        }
+        let auth = match (&cli.auth, &cli.bearer) {
+            (Some(creds), _) => {
+                let (username, password) = match creds.split_once(':') {
+                    Some((u, p)) => (u.to_string(), Some(p.to_string())),
+                    None         => (creds.clone(), None),
+                };
+                if cli.digest {
+                    Auth::Digest { username, password }
+                } else {
+                    Auth::Basic { username, password }
+                }
+            }
+            (None, Some(token)) => Auth::Bearer { token: token.clone() },
+            (None, None)        => return Ok(None),
+        };
+        auth.with_prompted_password().map(Some)
+    }
+
+    /// Ask for a password that is required but missing: Basic and Digest
+    /// credentials, or the OAuth2 `password` grant.
+    pub fn with_prompted_password(self) -> Result<Self> {
+        match self {
+            Auth::Basic { username, password: None } => {
+                let password = Some(prompt_password(&username)?);
+                Ok(Auth::Basic { username, password })
+            }
+            Auth::Digest { username, password: None } => {
+                let password = Some(prompt_password(&username)?);
+                Ok(Auth::Digest { username, password })
+            }
+            Auth::OAuth2(mut cfg) if cfg.grant_type == GrantType::Password && cfg.password.is_none() => {
+                let username = cfg.username.clone().unwrap_or_default();
+                cfg.password = Some(prompt_password(&username)?);
+                Ok(Auth::OAuth2(cfg))
+            }
+            other => Ok(other),
+        }
+    }
+
+    /// Apply `f` to every user-supplied string (used for `{{var}}` expansion).
+    pub fn try_map_strings(&self, f: impl Fn(&str) -> Result<String>) -> Result<Self> {
+        Ok(match self {
+            Auth::Basic { username, password } => Auth::Basic {
+                username: f(username)?,
+                password: password.as_deref().map(&f).transpose()?,
+            },
+            Auth::Bearer { token } => Auth::Bearer { token: f(token)? },
+            Auth::Digest { username, password } => Auth::Digest {
+                username: f(username)?,
+                password: password.as_deref().map(&f).transpose()?,
+            },
+            Auth::OAuth2(cfg) => Auth::OAuth2(OAuth2Config {
+                token_url:     f(&cfg.token_url)?,
+                client_id:     f(&cfg.client_id)?,
+                client_secret: cfg.client_secret.as_deref().map(&f).transpose()?,
+                scopes:        cfg.scopes.iter().map(|s| f(s)).collect::<Result<_>>()?,
+                grant_type:    cfg.grant_type,
+                username:      cfg.username.as_deref().map(&f).transpose()?,
+                password:      cfg.password.as_deref().map(&f).transpose()?,
+                refresh_token: cfg.refresh_token.as_deref().map(&f).transpose()?,
+            }),
+            Auth::AwsSigV4(cfg) => Auth::AwsSigV4(AwsSigV4Config {
+                access_key_id:     cfg.access_key_id.as_deref().map(&f).transpose()?,
+                secret_access_key: cfg.secret_access_key.as_deref().map(&f).transpose()?,
+                session_token:     cfg.session_token.as_deref().map(&f).transpose()?,
+                region:            cfg.region.as_deref().map(&f).transpose()?,
+                service:           f(&cfg.service)?,
+            }),
+            Auth::Hmac(cfg) => Auth::Hmac(HmacConfig {
+                secret: f(&cfg.secret)?,
+                ..cfg.clone()
+            }),
+        })
+    }
+
+    /// Copy with secrets replaced by `REDACTED`, safe to persist.
+    pub fn redacted(&self) -> Self {
+        match self {
+            Auth::Basic { username, password } => Auth::Basic {
+                username: username.clone(),
+                password: password.as_ref().map(|_| REDACTED.to_string()),
+            },
+            Auth::Bearer { .. } => Auth::Bearer { token: REDACTED.to_string() },
+            Auth::Digest { username, password } => Auth::Digest {
+                username: username.clone(),
+                password: password.as_ref().map(|_| REDACTED.to_string()),
+            },
+            Auth::OAuth2(cfg) => {
+                let mask = |s: &Option<String>| s.as_ref().map(|_| REDACTED.to_string());
+                Auth::OAuth2(OAuth2Config {
+                    client_secret: mask(&cfg.client_secret),
+                    password:      mask(&cfg.password),
+                    refresh_token: mask(&cfg.refresh_token),
+                    ..cfg.clone()
+                })
+            }
+            Auth::AwsSigV4(cfg) => {
+                let mask = |s: &Option<String>| s.as_ref().map(|_| REDACTED.to_string());
+                Auth::AwsSigV4(AwsSigV4Config {
+                    secret_access_key: mask(&cfg.secret_access_key),
+                    session_token:     mask(&cfg.session_token),
+                    ..cfg.clone()
+                })
+            }
+            Auth::Hmac(cfg) => Auth::Hmac(HmacConfig {
+                secret: REDACTED.to_string(),
+                ..cfg.clone()
+            }),
+        }
+    }
+
+    /// Apply static credentials to an outgoing request. Digest responses,
+    /// OAuth2 tokens and signatures are computed by `HttpClient` instead.
+    pub fn apply(&self, builder: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            Auth::Basic { username, password } => builder.basic_auth(username, password.as_ref()),
+            Auth::Bearer { token }             => builder.bearer_auth(token),
+            Auth::Digest { .. }                => builder,
+            Auth::OAuth2(_)                    => builder,
+            Auth::AwsSigV4(_)                  => builder,
+            Auth::Hmac(_)                      => builder,
+        }
+    }
+}
+
+fn prompt_password(username: &str) -> Result<String> {
+    rpassword::prompt_password(format!("Password for {username}: "))
+        .map_err(|e| AppError::Other(format!("Could not read password: {e}")))
+}
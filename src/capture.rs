@@ -0,0 +1,93 @@
+use crate::environment::Variables;
+use crate::error::{AppError, Result};
+use crate::jsonpath;
+use crate::redact::Redaction;
+use crate::response::ParsedResponse;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Values to extract from a response into the run's variable scope.
+///
+/// ```json
+/// "capture": {
+///   "body":    { "token": "$.data.access_token" },
+///   "headers": { "request_id": "X-Request-Id" }
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Capture {
+    /// Variable name → JSON pointer or JSONPath into the response body.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub body:    BTreeMap<String, String>,
+    /// Variable name → response header name (case-insensitive).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+}
+
+impl Capture {
+    pub fn is_empty(&self) -> bool {
+        self.body.is_empty() && self.headers.is_empty()
+    }
+
+    /// Extract every configured value from `resp` and insert it into `vars`.
+    /// Returns the captured `(name, value)` pairs in a stable order.
+    pub fn apply(&self, resp: &ParsedResponse, vars: &mut Variables) -> Result<Vec<(String, String)>> {
+        let mut captured = Vec::new();
+
+        if !self.body.is_empty() {
+            let json: serde_json::Value = serde_json::from_str(&resp.text()).map_err(|_| {
+                AppError::Capture {
+                    name:   self.body.keys().next().cloned().unwrap_or_default(),
+                    reason: "response body is not valid JSON".into(),
+                }
+            })?;
+            for (name, expr) in &self.body {
+                let value = jsonpath::select(&json, expr)?.ok_or_else(|| AppError::Capture {
+                    name:   name.clone(),
+                    reason: format!("no match for '{expr}'"),
+                })?;
+                captured.push((name.clone(), value_to_string(value)));
+            }
+        }
+
+        for (name, header) in &self.headers {
+            let value = resp
+                .headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(header))
+                .map(|(_, v)| v.clone())
+                .ok_or_else(|| AppError::Capture {
+                    name:   name.clone(),
+                    reason: format!("response has no '{header}' header"),
+                })?;
+            captured.push((name.clone(), value));
+        }
+
+        for (name, value) in &captured {
+            vars.insert(name.clone(), value.clone());
+        }
+        Ok(captured)
+    }
+
+    /// Whether the capture `name` holds a value `redact` would hide: the name
+    /// or the last key of its body path is a redacted field, or its header
+    /// is a redacted header.
+    pub fn is_sensitive(&self, name: &str, redact: &Redaction) -> bool {
+        if redact.is_sensitive_field(name) {
+            return true;
+        }
+        if let Some(path) = self.body.get(name) {
+            let last = path.rsplit(['.', '/', '[', ']', '\'', '"']).find(|s| !s.is_empty());
+            return redact.body_paths.iter().any(|p| p == path) || last.is_some_and(|k| redact.is_sensitive_field(k));
+        }
+        self.headers.get(name).is_some_and(|h| redact.is_sensitive_header(h))
+    }
+}
+
+/// Strings are captured verbatim; any other JSON value as its JSON text.
+fn value_to_string(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        other                        => other.to_string(),
+    }
+}
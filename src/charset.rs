@@ -0,0 +1,130 @@
+//! Character set detection and decoding for text response bodies.
+//!
+//! Precedence, highest first: the `--encoding` override, a byte order mark,
+//! the `charset` parameter of `Content-Type`, an HTML `<meta>` or XML
+//! declaration, and finally UTF-8.
+
+use crate::cli::Cli;
+use crate::error::{AppError, Result};
+use encoding_rs::{Encoding, UTF_16BE, UTF_16LE, UTF_8};
+use std::borrow::Cow;
+
+/// How many leading bytes are scanned for `<meta charset>` / `<?xml encoding>`.
+const PRESCAN_LEN: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharsetSource {
+    Override,
+    Bom,
+    ContentType,
+    Document,
+    Default,
+}
+
+/// The encoding a body is decoded with and where it came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Charset {
+    pub encoding: &'static Encoding,
+    pub source:   CharsetSource,
+}
+
+impl Default for Charset {
+    fn default() -> Self {
+        Self { encoding: UTF_8, source: CharsetSource::Default }
+    }
+}
+
+impl std::fmt::Display for Charset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let source = match self.source {
+            CharsetSource::Override    => "--encoding",
+            CharsetSource::Bom         => "byte order mark",
+            CharsetSource::ContentType => "Content-Type",
+            CharsetSource::Document    => "document",
+            CharsetSource::Default     => "default",
+        };
+        write!(f, "{} ({source})", self.encoding.name())
+    }
+}
+
+impl Charset {
+    /// Detect the charset of `body`, honouring an explicit override first.
+    pub fn detect(content_type: &str, body: &[u8], forced: Option<&'static Encoding>) -> Self {
+        if let Some(encoding) = forced {
+            return Self { encoding, source: CharsetSource::Override };
+        }
+        if let Some((encoding, _)) = Encoding::for_bom(body) {
+            return Self { encoding, source: CharsetSource::Bom };
+        }
+        if let Some(encoding) = content_type_charset(content_type) {
+            return Self { encoding, source: CharsetSource::ContentType };
+        }
+        let mime = content_type.to_ascii_lowercase();
+        if mime.contains("html") || mime.contains("xml") {
+            if let Some(encoding) = document_charset(&body[..body.len().min(PRESCAN_LEN)]) {
+                return Self { encoding, source: CharsetSource::Document };
+            }
+        }
+        Self::default()
+    }
+
+    /// Decode `body`, dropping a byte order mark only when one was detected.
+    pub fn decode<'a>(&self, body: &'a [u8]) -> Cow<'a, str> {
+        match self.source {
+            CharsetSource::Bom => self.encoding.decode_with_bom_removal(body).0,
+            _                  => self.encoding.decode_without_bom_handling(body).0,
+        }
+    }
+}
+
+/// Resolve an `--encoding` label such as `latin1` or `Shift_JIS`.
+pub fn from_label(label: &str) -> Result<&'static Encoding> {
+    Encoding::for_label(label.trim().as_bytes())
+        .ok_or_else(|| AppError::Other(format!("Unknown encoding '{label}'")))
+}
+
+/// The `--encoding` override, if one was given.
+pub fn from_cli(cli: &Cli) -> Result<Option<&'static Encoding>> {
+    cli.encoding.as_deref().map(from_label).transpose()
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+fn content_type_charset(content_type: &str) -> Option<&'static Encoding> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        if !name.trim().eq_ignore_ascii_case("charset") {
+            return None;
+        }
+        Encoding::for_label(value.trim().trim_matches(['"', '\'']).as_bytes())
+    })
+}
+
+/// Find `<meta charset=…>`, `<meta http-equiv … content="…; charset=…">` or
+/// `<?xml … encoding="…"?>` near the start of a document.
+fn document_charset(head: &[u8]) -> Option<&'static Encoding> {
+    let text = String::from_utf8_lossy(head).to_ascii_lowercase();
+
+    let label = if text.trim_start().starts_with("<?xml") {
+        let decl = &text[..text.find("?>")?];
+        attribute_value(decl, "encoding=")
+    } else {
+        text.match_indices("<meta")
+            .filter_map(|(start, _)| {
+                let end = text[start..].find('>').map_or(text.len(), |e| start + e);
+                attribute_value(&text[start..end], "charset=")
+            })
+            .next()
+    }?;
+
+    // A document cannot really declare itself UTF-16 in an ASCII-compatible tag.
+    Encoding::for_label(label.as_bytes()).map(|e| if e == UTF_16LE || e == UTF_16BE { UTF_8 } else { e })
+}
+
+/// The value following `key` in a tag, with surrounding quotes removed.
+fn attribute_value<'a>(tag: &'a str, key: &str) -> Option<&'a str> {
+    let rest  = &tag[tag.find(key)? + key.len()..];
+    let rest  = rest.trim_start_matches(['"', '\'', ' ']);
+    let end   = rest.find(['"', '\'', ' ', ';', '/', '>']).unwrap_or(rest.len());
+    Some(&rest[..end]).filter(|v| !v.is_empty())
+}
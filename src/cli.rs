@@ -1,4 +1,8 @@
-use clap::{Parser, Subcommand, ValueEnum};
+use crate::report::ReportSpec;
+use clap::error::ErrorKind;
+use clap::builder::BoolishValueParser;
+use clap::{ArgAction, CommandFactory, Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
 
 #[derive(Parser)]
 #[command(
@@ -21,18 +25,104 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub quiet: bool,
 
-    /// Disable TLS certificate verification.
-    #[arg(long, global = true)]
+    /// Disable TLS certificate verification; `--insecure=false` overrides `RR_INSECURE`.
+    #[arg(
+        long, global = true, env = "RR_INSECURE", value_name = "BOOL",
+        action = ArgAction::Set, num_args = 0..=1, require_equals = true,
+        default_value = "false", default_missing_value = "true",
+        value_parser = BoolishValueParser::new(), hide_default_value = true
+    )]
     pub insecure: bool,
 
-    /// Request timeout in seconds.
-    #[arg(long, global = true, default_value = "30", value_name = "SECS")]
-    pub timeout: u64,
+    /// Request timeout in seconds [default: config `timeout_secs`].
+    #[arg(long, global = true, env = "RR_TIMEOUT", value_name = "SECS")]
+    pub timeout: Option<u64>,
+
+    /// Do not follow redirects; `--no-follow=false` overrides `RR_NO_FOLLOW`.
+    #[arg(
+        long, global = true, env = "RR_NO_FOLLOW", value_name = "BOOL",
+        action = ArgAction::Set, num_args = 0..=1, require_equals = true,
+        default_value = "false", default_missing_value = "true",
+        value_parser = BoolishValueParser::new(), hide_default_value = true
+    )]
+    pub no_follow: bool,
+
+    /// Maximum number of redirects to follow [default: config `max_redirects`].
+    #[arg(long, global = true, env = "RR_MAX_REDIRECTS", value_name = "N", conflicts_with = "no_follow")]
+    pub max_redirects: Option<usize>,
+
+    /// Configuration profile to apply [default: config `default_profile`].
+    #[arg(short, long, global = true, env = "RR_PROFILE", value_name = "NAME")]
+    pub profile: Option<String>,
+
+    /// Basic authentication; prompts for the password when omitted.
+    #[arg(long, global = true, value_name = "USER[:PASS]", conflicts_with = "bearer")]
+    pub auth: Option<String>,
+
+    /// Send `--auth` credentials with HTTP Digest instead of Basic.
+    #[arg(long, global = true, requires = "auth")]
+    pub digest: bool,
+
+    /// Bearer token authentication.
+    #[arg(long, global = true, value_name = "TOKEN")]
+    pub bearer: Option<String>,
+
+    /// Sign requests with AWS SigV4 using `AWS_*` credentials from the environment.
+    #[arg(long, global = true, value_name = "REGION:SERVICE", conflicts_with_all = ["auth", "bearer"])]
+    pub aws_sigv4: Option<String>,
+
+    /// Show redacted headers in verbose output. History stays redacted.
+    #[arg(long, global = true)]
+    pub show_secrets: bool,
+
+    /// Stream the response body to FILE instead of printing it.
+    #[arg(short, long, global = true, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Save the response body under the name from `Content-Disposition` or the URL.
+    #[arg(long, global = true)]
+    pub download: bool,
+
+    /// Resume a partial `--output` file with a Range request.
+    #[arg(long, global = true)]
+    pub resume: bool,
+
+    /// Overwrite an existing file with `--download` instead of refusing.
+    #[arg(long, global = true, requires = "download", conflicts_with = "resume")]
+    pub force: bool,
+
+    /// Filter a JSON response body with a jq-like expression, e.g. `.items[] | .id`.
+    #[arg(long, global = true, value_name = "EXPR", conflicts_with_all = ["output", "download"])]
+    pub filter: Option<String>,
+
+    /// Decode text responses with this charset, e.g. `latin1` or `Shift_JIS`.
+    #[arg(long, global = true, value_name = "CHARSET")]
+    pub encoding: Option<String>,
+
+    /// Environment used to expand `{{var}}` placeholders.
+    #[arg(short, long, global = true, value_name = "NAME")]
+    pub env: Option<String>,
 
     #[command(subcommand)]
     pub command: Command,
 }
 
+impl Cli {
+    /// Cross-argument checks clap cannot express for global arguments: an
+    /// `ArgGroup` on `Cli` is not propagated to the subcommands.
+    pub fn validate(&self) -> Result<(), clap::Error> {
+        // `--download` names the file from the response, so the partial file
+        // to resume could not be found before sending the request.
+        if self.resume && self.output.is_none() {
+            return Err(Cli::command().error(
+                ErrorKind::MissingRequiredArgument,
+                "--resume requires --output <FILE>",
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum OutputFormat {
     /// Colorized, pretty-printed output.
@@ -53,7 +143,7 @@ pub enum Command {
         #[arg(short = 'H', long = "header", value_name = "HEADER")]
         headers: Vec<String>,
         /// Query parameters, e.g. `page=1`.
-        #[arg(short, long, value_name = "KEY=VALUE")]
+        #[arg(long, value_name = "KEY=VALUE")]
         query: Vec<String>,
     },
 
@@ -62,12 +152,15 @@ pub enum Command {
         url: String,
         #[arg(short = 'H', long = "header", value_name = "HEADER")]
         headers: Vec<String>,
-        /// JSON body or path prefixed with `@`, e.g. `@body.json`.
+        /// JSON body, or a file prefixed with `@` (e.g. `@body.json`); `@-` reads stdin.
         #[arg(short, long, value_name = "JSON|@FILE")]
         body: Option<String>,
         /// Form fields, e.g. `name=Alice`.
-        #[arg(short, long, value_name = "KEY=VALUE")]
+        #[arg(long, value_name = "KEY=VALUE", conflicts_with = "body")]
         form: Vec<String>,
+        /// Multipart fields, e.g. `title=Hi` or `avatar=@a.png;type=image/png`.
+        #[arg(short = 'F', long = "field", value_name = "NAME=VALUE|@FILE", conflicts_with_all = ["body", "form"])]
+        multipart: Vec<String>,
     },
 
     /// Send a PUT request.
@@ -77,6 +170,9 @@ pub enum Command {
         headers: Vec<String>,
         #[arg(short, long, value_name = "JSON|@FILE")]
         body: Option<String>,
+        /// Multipart fields, e.g. `title=Hi` or `avatar=@a.png;type=image/png`.
+        #[arg(short = 'F', long = "field", value_name = "NAME=VALUE|@FILE", conflicts_with = "body")]
+        multipart: Vec<String>,
     },
 
     /// Send a PATCH request.
@@ -86,6 +182,9 @@ pub enum Command {
         headers: Vec<String>,
         #[arg(short, long, value_name = "JSON|@FILE")]
         body: Option<String>,
+        /// Multipart fields, e.g. `title=Hi` or `avatar=@a.png;type=image/png`.
+        #[arg(short = 'F', long = "field", value_name = "NAME=VALUE|@FILE", conflicts_with = "body")]
+        multipart: Vec<String>,
     },
 
     /// Send a DELETE request.
@@ -124,6 +223,12 @@ pub enum Command {
         action: ConfigAction,
     },
 
+    /// Manage secrets referenced as `{{secret:name}}`.
+    Secret {
+        #[command(subcommand)]
+        action: SecretAction,
+    },
+
     /// Launch the interactive TUI (Phase 3).
     Tui,
 }
@@ -134,18 +239,48 @@ pub enum CollectionAction {
     List,
     /// Show requests inside a collection.
     Show { name: String },
-    /// Execute a saved request.
-    Run { collection: String, request: String },
+    /// Execute a saved request, or every request in the collection.
+    Run {
+        collection: String,
+        /// Request to run; omit to run the whole collection in order.
+        request: Option<String>,
+        /// Run every request in the collection as a test suite.
+        #[arg(long, conflicts_with = "request")]
+        all: bool,
+        /// Write a report of a whole-collection run, e.g. `junit:out.xml`
+        /// or `json:out.json`. May be repeated.
+        #[arg(long, value_name = "FORMAT:PATH", conflicts_with = "request")]
+        report: Vec<ReportSpec>,
+    },
     /// Delete a collection.
     Delete { name: String },
 }
 
+#[derive(Subcommand)]
+pub enum SecretAction {
+    /// Store a secret; prompts for the value when omitted.
+    Set { name: String, value: Option<String> },
+    /// Print a secret's value.
+    Get { name: String },
+    /// List secret names.
+    List,
+    /// Remove a secret.
+    Rm { name: String },
+}
+
 #[derive(Subcommand)]
 pub enum ConfigAction {
     /// Print current configuration.
     Show,
-    /// Set a configuration key.
+    /// Print a single configuration value.
+    Get { key: String },
+    /// Set a configuration key, e.g. `timeout_secs 10` or
+    /// `default_headers.X-Api-Key secret`.
     Set { key: String, value: String },
+    /// Reset a configuration key to its default.
+    Unset { key: String },
+    /// Make a profile the default for future commands.
+    Use { profile: String },
     /// Reset all settings to defaults.
     Reset,
 }
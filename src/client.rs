@@ -1,41 +1,248 @@
+use crate::auth::Auth;
+use crate::cli::Cli;
 use crate::config::AppConfig;
+use crate::digest::{self, Challenge};
+use crate::display;
+use crate::environment;
 use crate::error::{AppError, Result};
-use crate::request::{HttpMethod, HttpRequest, RequestBody};
-use reqwest::Client;
-use std::time::Duration;
+use crate::history;
+use crate::request::{HttpMethod, HttpRequest, MultipartField, RequestBody};
+use crate::oauth::{OAuth2Config, TokenCache};
+use crate::response::{self, ParsedResponse};
+use crate::settings::Settings;
+use crate::signature;
+use crate::sigv4;
+use crate::vault::{self, Vault};
+use chrono::Utc;
+use encoding_rs::Encoding;
+use futures_util::StreamExt;
+use http_body_util::BodyExt;
+use indicatif::{ProgressBar, ProgressStyle};
+use reqwest::header::{HeaderValue, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, WWW_AUTHENTICATE};
+use reqwest::multipart::{Form, Part};
+use reqwest::{Client, StatusCode};
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio_util::io::ReaderStream;
 
 pub struct HttpClient {
-    inner: Client,
+    inner:  Client,
+    tokens: TokenCache,
+    /// Last Digest challenge per origin and the nonce count used with it.
+    digest: Mutex<HashMap<String, (Challenge, u32)>>,
+    /// Unlocked on the first request that references `{{secret:name}}`.
+    vault:  Mutex<Option<Vault>>,
+    /// `--body @-` read into memory, for auth modes that may resend the body.
+    stdin:  Mutex<Option<Vec<u8>>>,
+    /// Show progress bars for large transfers.
+    progress: bool,
+    /// `--encoding` override for decoding text responses.
+    encoding: Option<&'static Encoding>,
 }
 
 impl HttpClient {
-    /// Create a client configured from `AppConfig`.
+    /// Create a client configured from `AppConfig` alone.
     pub fn new(config: &AppConfig) -> Result<Self> {
-        Self::with_options(config.timeout_secs, config.follow_redirects, false)
+        Self::from_settings(&Settings::from_config(config))
     }
 
-    /// Create a client with explicit options (used by the binary entry point).
-    pub fn with_options(timeout_secs: u64, follow_redirects: bool, insecure: bool) -> Result<Self> {
-        let redirect_policy = if follow_redirects {
-            reqwest::redirect::Policy::limited(10)
+    /// Create a client from fully resolved settings.
+    pub fn from_settings(settings: &Settings) -> Result<Self> {
+        let redirect_policy = if settings.follow_redirects && settings.max_redirects > 0 {
+            reqwest::redirect::Policy::limited(settings.max_redirects)
         } else {
             reqwest::redirect::Policy::none()
         };
 
         let mut builder = Client::builder()
-            .timeout(Duration::from_secs(timeout_secs))
+            .timeout(Duration::from_secs(settings.timeout_secs))
             .redirect(redirect_policy)
             .user_agent(concat!("rustrest/", env!("CARGO_PKG_VERSION")));
 
-        if insecure {
+        if settings.insecure {
             builder = builder.danger_accept_invalid_certs(true);
         }
 
-        Ok(Self { inner: builder.build().map_err(AppError::Http)? })
+        Ok(Self {
+            inner:  builder.build().map_err(AppError::Http)?,
+            tokens: TokenCache::default(),
+            digest: Mutex::new(HashMap::new()),
+            vault:  Mutex::new(None),
+            stdin:  Mutex::new(None),
+            progress: false,
+            encoding: None,
+        })
+    }
+
+    /// Store OAuth2 tokens at `path` instead of the config directory.
+    pub fn with_token_cache(mut self, path: PathBuf) -> Self {
+        self.tokens = TokenCache::at(path);
+        self
+    }
+
+    /// Show a progress bar on stderr for large uploads and downloads.
+    pub fn with_progress(mut self, enabled: bool) -> Self {
+        self.progress = enabled;
+        self
+    }
+
+    pub fn shows_progress(&self) -> bool {
+        self.progress
+    }
+
+    /// Decode text responses with `encoding` instead of the detected charset.
+    pub fn with_encoding(mut self, encoding: Option<&'static Encoding>) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Resolve `{{secret:name}}` references from an already unlocked vault.
+    pub fn with_vault(self, vault: Vault) -> Self {
+        *self.vault.lock().expect("vault state poisoned") = Some(vault);
+        self
     }
 
     /// Execute an `HttpRequest` and return the raw reqwest response.
+    ///
+    /// OAuth2 auth is resolved to a bearer token first; a 401 response forces
+    /// one token refresh and retry. Digest auth answers the server's 401
+    /// challenge and retries once. Both buffer a stdin body so the retry can
+    /// resend it. AWS SigV4 and HMAC sign the final built request.
+    /// `{{secret:name}}` references are resolved here, so callers never hold
+    /// the plain-text values.
     pub async fn send(&self, req: HttpRequest) -> Result<reqwest::Response> {
+        let req = self.resolve_secrets(req)?;
+        match &req.auth {
+            Some(Auth::OAuth2(cfg)) => self.send_oauth2(&req, cfg).await,
+            Some(Auth::Digest { username, password }) => {
+                self.send_digest(&req, username, password.as_deref().unwrap_or_default()).await
+            }
+            Some(Auth::AwsSigV4(cfg)) => {
+                let mut request = self.build(&req, None, true)?.build()?;
+                buffer_body(&mut request).await?;
+                sigv4::sign(&mut request, &cfg.resolve()?, Utc::now())?;
+                self.inner.execute(request).await.map_err(AppError::Http)
+            }
+            Some(Auth::Hmac(cfg)) => {
+                let mut request = self.build(&req, None, true)?.build()?;
+                buffer_body(&mut request).await?;
+                signature::sign(&mut request, cfg, Utc::now())?;
+                self.inner.execute(request).await.map_err(AppError::Http)
+            }
+            _ => self.build(&req, None, false)?.send().await.map_err(AppError::Http),
+        }
+    }
+
+    async fn send_oauth2(&self, req: &HttpRequest, cfg: &OAuth2Config) -> Result<reqwest::Response> {
+        self.buffer_stdin(req)?;
+        let token = self.tokens.access_token(&self.inner, cfg, false).await?;
+        let resp  = self.build(req, Some(&token), false)?.send().await?;
+        if resp.status() != StatusCode::UNAUTHORIZED {
+            return Ok(resp);
+        }
+
+        let token = self.tokens.access_token(&self.inner, cfg, true).await?;
+        self.build(req, Some(&token), false)?.send().await.map_err(AppError::Http)
+    }
+
+    /// Send preemptively when a challenge for this origin is known, otherwise
+    /// wait for the server's challenge.
+    async fn send_digest(&self, req: &HttpRequest, username: &str, password: &str) -> Result<reqwest::Response> {
+        self.buffer_stdin(req)?;
+        let mut first = self.build(req, None, false)?.build()?;
+        let origin    = first.url().origin().ascii_serialization();
+        if let Some(value) = self.digest_header(&origin, &first, username, password)? {
+            first.headers_mut().insert(AUTHORIZATION, value);
+        }
+
+        let resp = self.inner.execute(first).await?;
+        if resp.status() != StatusCode::UNAUTHORIZED {
+            return Ok(resp);
+        }
+        let challenge = resp
+            .headers()
+            .get_all(WWW_AUTHENTICATE)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .find(|v| v.trim_start().to_ascii_lowercase().starts_with("digest "));
+        let Some(challenge) = challenge else {
+            return Ok(resp);
+        };
+
+        let challenge = Challenge::parse(challenge)?;
+        self.digest.lock().expect("digest state poisoned").insert(origin.clone(), (challenge, 0));
+
+        let mut retry = self.build(req, None, false)?.build()?;
+        if let Some(value) = self.digest_header(&origin, &retry, username, password)? {
+            retry.headers_mut().insert(AUTHORIZATION, value);
+        }
+        self.inner.execute(retry).await.map_err(AppError::Http)
+    }
+
+    /// Answer the stored challenge for `origin`, bumping its nonce count.
+    fn digest_header(
+        &self,
+        origin: &str,
+        request: &reqwest::Request,
+        username: &str,
+        password: &str,
+    ) -> Result<Option<HeaderValue>> {
+        let mut state = self.digest.lock().expect("digest state poisoned");
+        let Some((challenge, nc)) = state.get_mut(origin) else {
+            return Ok(None);
+        };
+        *nc += 1;
+
+        let url = request.url();
+        let uri = match url.query() {
+            Some(q) => format!("{}?{q}", url.path()),
+            None    => url.path().to_string(),
+        };
+        let value = challenge.authorization(
+            username,
+            password,
+            request.method().as_str(),
+            &uri,
+            *nc,
+            &digest::cnonce(),
+        );
+        HeaderValue::from_str(&value)
+            .map(Some)
+            .map_err(|_| AppError::Other("Digest credentials contain invalid header characters".into()))
+    }
+
+    /// Read a `--body @-` body into memory once; later builds reuse it.
+    fn buffer_stdin(&self, req: &HttpRequest) -> Result<()> {
+        if !matches!(&req.body, Some(RequestBody::File(path)) if path == STDIN) {
+            return Ok(());
+        }
+        let mut stdin = self.stdin.lock().expect("stdin buffer poisoned");
+        if stdin.is_none() {
+            let mut bytes = Vec::new();
+            std::io::stdin().read_to_end(&mut bytes)?;
+            *stdin = Some(bytes);
+        }
+        Ok(())
+    }
+
+    fn resolve_secrets(&self, req: HttpRequest) -> Result<HttpRequest> {
+        if !vault::references_secrets(&serde_json::to_string(&req)?) {
+            return Ok(req);
+        }
+        let mut vault = self.vault.lock().expect("vault state poisoned");
+        if vault.is_none() {
+            *vault = Some(Vault::unlock()?);
+        }
+        let vault = vault.as_ref().expect("vault unlocked above");
+        environment::map_strings(&req, &|s| vault.expand(s))
+    }
+
+    /// `buffered` reads file and stdin bodies into memory, for auth modes
+    /// that sign the body bytes; otherwise they are streamed.
+    fn build(&self, req: &HttpRequest, bearer: Option<&str>, buffered: bool) -> Result<reqwest::RequestBuilder> {
         let mut builder = match req.method {
             HttpMethod::Get    => self.inner.get(&req.url),
             HttpMethod::Post   => self.inner.post(&req.url),
@@ -49,21 +256,191 @@ impl HttpClient {
             builder = builder.header(key, value);
         }
 
+        if let Some(auth) = &req.auth {
+            builder = auth.apply(builder);
+        }
+        if let Some(token) = bearer {
+            builder = builder.bearer_auth(token);
+        }
+
         if !req.query.is_empty() {
             builder = builder.query(&req.query.iter().collect::<Vec<_>>());
         }
 
-        builder = match req.body {
-            Some(RequestBody::Json(v))  => builder.json(&v),
-            Some(RequestBody::Form(map)) => builder.form(&map),
-            Some(RequestBody::Text(s))  => builder.header("Content-Type", "text/plain").body(s),
+        builder = match &req.body {
+            Some(RequestBody::Json(v))  => builder.json(v),
+            Some(RequestBody::Form(map)) => builder.form(map),
+            Some(RequestBody::Text(s))  => builder.header("Content-Type", "text/plain").body(s.clone()),
             Some(RequestBody::File(path)) => {
-                let bytes = std::fs::read(&path)?;
-                builder.body(bytes)
+                let has_type = req.headers.keys().any(|k| k.eq_ignore_ascii_case("content-type"));
+                if let Some(mime) = mime_guess::from_path(path).first().filter(|_| !has_type && path != STDIN) {
+                    builder = builder.header(CONTENT_TYPE, mime.essence_str());
+                }
+                self.file_body(builder, path, buffered)?
             }
+            Some(RequestBody::Multipart(fields)) => builder.multipart(multipart_form(fields)?),
             None => builder,
         };
 
-        builder.send().await.map_err(AppError::Http)
+        Ok(builder)
+    }
+
+    /// Attach a file (or stdin for `-`) body. Files are sent with their
+    /// `Content-Length`; stdin is sent chunked.
+    fn file_body(&self, builder: reqwest::RequestBuilder, path: &str, buffered: bool) -> Result<reqwest::RequestBuilder> {
+        if path == STDIN {
+            if let Some(bytes) = self.stdin.lock().expect("stdin buffer poisoned").clone() {
+                return Ok(builder.body(bytes));
+            }
+            if buffered {
+                let mut bytes = Vec::new();
+                std::io::stdin().read_to_end(&mut bytes)?;
+                return Ok(builder.body(bytes));
+            }
+            let stream = ReaderStream::new(tokio::io::stdin());
+            return Ok(builder.body(reqwest::Body::wrap_stream(stream)));
+        }
+
+        if buffered {
+            return Ok(builder.body(std::fs::read(path)?));
+        }
+        let file = std::fs::File::open(path)
+            .map_err(|e| AppError::Other(format!("Cannot open '{path}': {e}")))?;
+        let len    = file.metadata()?.len();
+        let stream = ReaderStream::new(tokio::fs::File::from_std(file));
+        let body   = if self.progress && len >= PROGRESS_THRESHOLD {
+            let bar = transfer_bar(Some(len), "Uploading");
+            reqwest::Body::wrap_stream(stream.inspect(move |chunk| {
+                if let Ok(chunk) = chunk {
+                    bar.inc(chunk.len() as u64);
+                    if bar.position() >= len {
+                        bar.finish_and_clear();
+                    }
+                }
+            }))
+        } else {
+            reqwest::Body::wrap_stream(stream)
+        };
+        Ok(builder.header(CONTENT_LENGTH, len).body(body))
+    }
+}
+
+/// Send `req`, print the response according to CLI flags and record the
+/// exchange in history. Shared by ad-hoc commands and collection runs.
+pub async fn execute(
+    http: &HttpClient,
+    req: &HttpRequest,
+    cli: &Cli,
+    config: &AppConfig,
+) -> Result<ParsedResponse> {
+    if cli.verbose {
+        display::print_request_line(&req.method.to_string(), &req.url);
+        let hdrs: Vec<_> = if cli.show_secrets {
+            req.headers.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+        } else {
+            config.redact.headers(&req.headers)
+        };
+        display::print_request_headers(&hdrs);
+    }
+
+    let mut outgoing = req.clone();
+    outgoing.auth = outgoing.auth.map(|a| a.with_prompted_password()).transpose()?;
+    let parsed = exchange(http, &outgoing, config).await?;
+    if cli.verbose && !cli.show_secrets {
+        // Mask response headers such as `Set-Cookie` too; the body is untouched.
+        let shown = ParsedResponse {
+            headers: config.redact.headers(parsed.headers.iter().map(|(k, v)| (k, v))),
+            ..parsed.clone()
+        };
+        response::print_parsed(&shown, cli)?;
+    } else {
+        response::print_parsed(&parsed, cli)?;
+    }
+    Ok(parsed)
+}
+
+/// Send `req`, parse the response and record it in history without printing.
+/// Missing passwords are not prompted for here; callers resolve them first.
+pub async fn exchange(http: &HttpClient, req: &HttpRequest, config: &AppConfig) -> Result<ParsedResponse> {
+    let start   = Instant::now();
+    let resp    = http.send(req.clone()).await?;
+    let elapsed = start.elapsed().as_millis();
+
+    let parsed = response::parse_response(resp, elapsed, http.encoding).await?;
+    history::record(req, parsed.status, elapsed, config)?;
+    Ok(parsed)
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+/// `--body @-` reads the body from stdin.
+const STDIN: &str = "-";
+
+/// Transfers smaller than this are not worth a progress bar.
+const PROGRESS_THRESHOLD: u64 = 1024 * 1024;
+
+/// A byte-count progress bar on stderr; `len` is `None` when unknown.
+pub(crate) fn transfer_bar(len: Option<u64>, label: &str) -> ProgressBar {
+    let bar = match len {
+        Some(len) => ProgressBar::new(len).with_style(
+            ProgressStyle::with_template("{msg} [{bar:30}] {bytes}/{total_bytes} {bytes_per_sec} {eta}")
+                .expect("valid progress template")
+                .progress_chars("=> "),
+        ),
+        None => ProgressBar::new_spinner().with_style(
+            ProgressStyle::with_template("{msg} {spinner} {bytes} {bytes_per_sec}").expect("valid progress template"),
+        ),
+    };
+    bar.with_message(label.to_string())
+}
+
+/// Collect a streamed body (multipart forms) into memory so its bytes can be
+/// signed. Bodies that are already buffered are left alone.
+async fn buffer_body(request: &mut reqwest::Request) -> Result<()> {
+    let Some(body) = request.body_mut().take() else { return Ok(()) };
+    let body = match body.as_bytes() {
+        Some(_) => body,
+        None    => body.collect().await.map_err(AppError::Http)?.to_bytes().into(),
+    };
+    *request.body_mut() = Some(body);
+    Ok(())
+}
+
+/// Build a multipart form; file parts are streamed from disk with a known length.
+fn multipart_form(fields: &[MultipartField]) -> Result<Form> {
+    let mut form = Form::new();
+    for field in fields {
+        let mut part = match &field.file {
+            Some(path) => {
+                let file = std::fs::File::open(path)
+                    .map_err(|e| AppError::Other(format!("Cannot open '{path}': {e}")))?;
+                let len  = file.metadata()?.len();
+                let name = field.filename.clone().or_else(|| {
+                    Path::new(path).file_name().map(|n| n.to_string_lossy().into_owned())
+                });
+                let mut part = Part::stream_with_length(reqwest::Body::from(tokio::fs::File::from_std(file)), len);
+                if let Some(mime) = mime_guess::from_path(path).first() {
+                    part = part.mime_str(mime.essence_str()).map_err(AppError::Http)?;
+                }
+                match name {
+                    Some(n) => part.file_name(n),
+                    None    => part,
+                }
+            }
+            None => {
+                let part = Part::text(field.value.clone().unwrap_or_default());
+                match &field.filename {
+                    Some(n) => part.file_name(n.clone()),
+                    None    => part,
+                }
+            }
+        };
+        if let Some(ct) = &field.content_type {
+            part = part
+                .mime_str(ct)
+                .map_err(|_| AppError::Other(format!("Invalid content type '{ct}' for field '{}'", field.name)))?;
+        }
+        form = form.part(field.name.clone(), part);
     }
+    Ok(form)
 }
@@ -1,8 +1,17 @@
-use crate::cli::CollectionAction;
+use crate::assertion::Assertions;
+use crate::auth::Auth;
+use crate::capture::Capture;
+use crate::charset;
+use crate::cli::{Cli, CollectionAction};
+use crate::client::{self, HttpClient};
 use crate::config::AppConfig;
 use crate::display;
+use crate::environment;
 use crate::error::{AppError, Result};
 use crate::request::HttpRequest;
+use crate::runner;
+use crate::settings::Settings;
+use colored::Colorize;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -11,77 +20,172 @@ use std::path::PathBuf;
 pub struct Collection {
     pub name:        String,
     pub description: Option<String>,
+    /// Execution order for whole-collection runs. Requests not listed here
+    /// run afterwards in alphabetical order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order:       Vec<String>,
     pub requests:    HashMap<String, SavedRequest>,
 }
 
+impl Collection {
+    /// Requests in deterministic run order, paired with their keys.
+    pub fn ordered_requests(&self) -> Vec<(&str, &SavedRequest)> {
+        let mut out: Vec<(&str, &SavedRequest)> = self
+            .order
+            .iter()
+            .filter_map(|k| self.requests.get_key_value(k))
+            .map(|(k, r)| (k.as_str(), r))
+            .collect();
+
+        let mut rest: Vec<_> = self
+            .requests
+            .iter()
+            .filter(|(k, _)| !self.order.contains(k))
+            .map(|(k, r)| (k.as_str(), r))
+            .collect();
+        rest.sort_by_key(|(k, _)| *k);
+
+        out.extend(rest);
+        out
+    }
+
+    /// Reject an `order` list that names a missing request or lists one twice,
+    /// so a typo cannot silently reorder a test suite.
+    pub fn validate(&self) -> Result<()> {
+        for (i, key) in self.order.iter().enumerate() {
+            let problem = if !self.requests.contains_key(key) {
+                "names a request that does not exist"
+            } else if self.order[..i].contains(key) {
+                "lists a request more than once"
+            } else {
+                continue;
+            };
+            return Err(AppError::Other(format!(
+                "Collection '{}': `order` entry '{key}' {problem}",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SavedRequest {
     pub name:        String,
     pub description: Option<String>,
     pub request:     HttpRequest,
+    /// Values extracted from the response for use by later requests.
+    #[serde(default, skip_serializing_if = "Capture::is_empty")]
+    pub capture:     Capture,
+    /// Checks the response must pass for the request to count as passed.
+    #[serde(default, rename = "assert", skip_serializing_if = "Assertions::is_empty")]
+    pub assertions:  Assertions,
 }
 
 // ── public API ────────────────────────────────────────────────────────────────
 
-pub fn handle_action(action: &CollectionAction, config: &AppConfig) -> Result<()> {
+pub async fn handle_action(action: &CollectionAction, cli: &Cli, config: &AppConfig) -> Result<()> {
     match action {
         CollectionAction::List                     => list(config),
         CollectionAction::Show { name }            => show(name, config),
-        CollectionAction::Run { collection, request } => run(collection, request, config),
+        CollectionAction::Run { collection, request: Some(request), all: false, .. } => {
+            run(collection, request, cli, config).await
+        }
+        CollectionAction::Run { collection, report, .. } => {
+            runner::run_collection(&load(config, collection)?, report, cli, config).await
+        }
         CollectionAction::Delete { name }          => delete(name, config),
     }
 }
 
-pub fn load(config: &AppConfig, name: &str) -> Result<Collection> {
-    let path = collection_path(config, name);
-    if !path.exists() {
-        return Err(AppError::CollectionNotFound { name: name.to_string() });
+/// Where a collection file was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionOrigin {
+    /// The project's `.rustrest/collections/` directory.
+    Project,
+    /// The global `collections_dir`.
+    Global,
+}
+
+impl std::fmt::Display for CollectionOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CollectionOrigin::Project => write!(f, "project"),
+            CollectionOrigin::Global  => write!(f, "global"),
+        }
     }
+}
+
+pub fn load(config: &AppConfig, name: &str) -> Result<Collection> {
+    let (_, path) = find(config, name)
+        .ok_or_else(|| AppError::CollectionNotFound { name: name.to_string() })?;
     let raw = std::fs::read_to_string(&path)?;
-    serde_json::from_str(&raw).map_err(AppError::Json)
+    let col: Collection = serde_json::from_str(&raw)?;
+    col.validate()?;
+    Ok(col)
 }
 
 pub fn save(config: &AppConfig, col: &Collection) -> Result<()> {
     let dir = &config.collections_dir;
     std::fs::create_dir_all(dir)?;
     std::fs::write(
-        collection_path(config, &col.name),
+        dir.join(format!("{}.json", col.name)),
         serde_json::to_string_pretty(col)?,
     )?;
     Ok(())
 }
 
-// ── private helpers ───────────────────────────────────────────────────────────
+/// Directories searched for collections, project first.
+pub fn search_dirs(config: &AppConfig) -> Vec<(CollectionOrigin, PathBuf)> {
+    let mut dirs = Vec::new();
+    if let Some(dir) = config.project_collections_dir() {
+        dirs.push((CollectionOrigin::Project, dir));
+    }
+    dirs.push((CollectionOrigin::Global, config.collections_dir.clone()));
+    dirs
+}
 
-fn collection_path(config: &AppConfig, name: &str) -> PathBuf {
-    config.collections_dir.join(format!("{name}.json"))
+/// Locate the collection file for `name`, preferring the project directory.
+pub fn find(config: &AppConfig, name: &str) -> Option<(CollectionOrigin, PathBuf)> {
+    search_dirs(config)
+        .into_iter()
+        .map(|(origin, dir)| (origin, dir.join(format!("{name}.json"))))
+        .find(|(_, path)| path.exists())
 }
 
-fn list(config: &AppConfig) -> Result<()> {
-    let dir = &config.collections_dir;
-    if !dir.exists() {
-        display::print_info("No collections found. Create one at ~/.config/rustrest/collections/");
-        return Ok(());
+/// Every available collection name with its origin, project first.
+pub fn list_all(config: &AppConfig) -> Result<Vec<(String, CollectionOrigin)>> {
+    let mut all = Vec::new();
+    for (origin, dir) in search_dirs(config) {
+        if !dir.exists() {
+            continue;
+        }
+        let mut names: Vec<String> = std::fs::read_dir(&dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|x| x == "json"))
+            .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().into_owned()))
+            .collect();
+        names.sort();
+        all.extend(names.into_iter().map(|n| (n, origin)));
     }
+    Ok(all)
+}
 
-    let mut entries: Vec<_> = std::fs::read_dir(dir)?
-        .filter_map(|e| e.ok())
-        .filter(|e| e.path().extension().map_or(false, |x| x == "json"))
-        .collect();
-
-    entries.sort_by_key(|e| e.file_name());
+// ── private helpers ───────────────────────────────────────────────────────────
 
+fn list(config: &AppConfig) -> Result<()> {
+    let entries = list_all(config)?;
     if entries.is_empty() {
-        display::print_info("No collections found.");
+        display::print_info("No collections found. Create one at ~/.config/rustrest/collections/ or .rustrest/collections/");
         return Ok(());
     }
 
     display::print_header("Collections");
-    for entry in entries {
-        let path = entry.path();
-        if let Some(stem) = path.file_stem() {
-            println!("  • {}", stem.to_string_lossy());
-        }
+    for (i, (name, origin)) in entries.iter().enumerate() {
+        let shadowed = entries[..i].iter().any(|(n, _)| n == name);
+        let note = if shadowed { ", shadowed by project" } else { "" };
+        println!("  • {name}  {}", format!("({origin}{note})").dimmed());
     }
     Ok(())
 }
@@ -93,10 +197,7 @@ fn show(name: &str, config: &AppConfig) -> Result<()> {
         println!("  {desc}");
     }
     println!();
-    let mut keys: Vec<_> = col.requests.keys().collect();
-    keys.sort();
-    for key in keys {
-        let r = &col.requests[key];
+    for (key, r) in col.ordered_requests() {
         println!(
             "  {:20}  {} {}",
             key.bright_cyan_display(),
@@ -117,29 +218,43 @@ impl BrightCyanDisplay for str {
     }
 }
 
-fn run(collection: &str, request_name: &str, config: &AppConfig) -> Result<()> {
+async fn run(collection: &str, request_name: &str, cli: &Cli, config: &AppConfig) -> Result<()> {
     let col = load(config, collection)?;
     let saved = col.requests.get(request_name).ok_or_else(|| {
         AppError::Other(format!(
             "Request '{request_name}' not found in collection '{collection}'"
         ))
     })?;
-    display::print_info(&format!(
-        "Loaded: {} {}",
-        saved.request.method, saved.request.url
-    ));
-    // Execution is delegated to the caller (main.rs) via the returned request.
-    // Phase 2: wire this up properly.
-    let _ = saved;
+    if !cli.quiet {
+        display::print_info(&format!(
+            "Running: {collection}/{request_name}  ({})",
+            saved.name
+        ));
+    }
+
+    let vars = environment::load_selected(cli.env.as_deref())?;
+    let mut req = environment::expand_request(&saved.request, &vars, config.base_url.as_deref())?;
+    if let Some(a) = Auth::from_cli(cli)? {
+        req.auth = Some(a);
+    }
+    let http = HttpClient::from_settings(&Settings::resolve(cli, config))?.with_encoding(charset::from_cli(cli)?);
+    let parsed = client::execute(&http, &req, cli, config).await?;
+
+    // Same rule as whole-collection runs: no `status` check means < 400.
+    let failures = saved.assertions.evaluate(&parsed);
+    if !failures.is_empty() {
+        for f in &failures {
+            display::print_error(&f.to_string());
+        }
+        return Err(AppError::AssertionsFailed { failed: failures.len() });
+    }
     Ok(())
 }
 
 fn delete(name: &str, config: &AppConfig) -> Result<()> {
-    let path = collection_path(config, name);
-    if !path.exists() {
-        return Err(AppError::CollectionNotFound { name: name.to_string() });
-    }
+    let (origin, path) = find(config, name)
+        .ok_or_else(|| AppError::CollectionNotFound { name: name.to_string() })?;
     std::fs::remove_file(&path)?;
-    display::print_success(&format!("Deleted collection '{name}'"));
+    display::print_success(&format!("Deleted {origin} collection '{name}'"));
     Ok(())
 }
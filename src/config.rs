@@ -1,12 +1,18 @@
+use crate::display;
 use crate::error::{AppError, Result};
+use crate::redact::Redaction;
 use dirs::config_dir;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 const APP_NAME: &str = "rustrest";
 
+/// Name of the project-local directory discovered from the working directory.
+pub const PROJECT_DIR_NAME: &str = ".rustrest";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     /// Default headers sent with every request.
     pub default_headers: HashMap<String, String>,
@@ -20,11 +26,34 @@ pub struct AppConfig {
     /// Whether to follow redirects.
     pub follow_redirects: bool,
 
+    /// Maximum number of redirects to follow.
+    pub max_redirects: usize,
+
     /// Maximum number of history entries to retain.
     pub max_history: usize,
 
     /// Directory where collections are stored.
     pub collections_dir: PathBuf,
+
+    /// Named sets of overrides, e.g. `{ "staging": { "base_url": "…" } }`.
+    /// Each top-level key in a profile replaces the base value as a whole.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub profiles: BTreeMap<String, serde_json::Value>,
+
+    /// Headers and body fields masked in history and verbose output.
+    pub redact: Redaction,
+
+    /// Profile applied when `--profile` is not given.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
+
+    /// Profile applied by `with_profile`, if any.
+    #[serde(skip)]
+    pub active_profile: Option<String>,
+
+    /// Project-local `.rustrest/` directory this config was layered with.
+    #[serde(skip)]
+    pub project_dir: Option<PathBuf>,
 }
 
 impl Default for AppConfig {
@@ -34,23 +63,114 @@ impl Default for AppConfig {
             base_url: None,
             timeout_secs: 30,
             follow_redirects: true,
+            max_redirects: 10,
             max_history: 100,
             collections_dir: Self::default_collections_dir(),
+            profiles: BTreeMap::new(),
+            redact: Redaction::default(),
+            default_profile: None,
+            active_profile: None,
+            project_dir: None,
         }
     }
 }
 
 impl AppConfig {
+    /// Load the global config, overlaid with the `.rustrest/config.json` of
+    /// the nearest project directory above the working directory, if any.
     pub fn load() -> Result<Self> {
-        let path = Self::config_path();
-        if path.exists() {
-            let content = std::fs::read_to_string(&path)?;
-            serde_json::from_str(&content).map_err(AppError::Json)
+        let project = std::env::current_dir()
+            .ok()
+            .and_then(|cwd| Self::find_project_dir(&cwd));
+        Self::load_from(&Self::config_path(), project.as_deref())
+    }
+
+    /// Load only the global config file. Use this before `save` so project
+    /// overrides are never written back to the global file.
+    pub fn load_global() -> Result<Self> {
+        Self::load_from(&Self::config_path(), None)
+    }
+
+    /// Load `global_path` and overlay `project_dir/config.json` on top of it.
+    /// Objects such as `default_headers` are merged key by key.
+    pub fn load_from(global_path: &Path, project_dir: Option<&Path>) -> Result<Self> {
+        let mut merged = read_json(global_path)?.unwrap_or(serde_json::Value::Null);
+        if let Some(dir) = project_dir {
+            if let Some(overlay) = read_json(&dir.join("config.json"))? {
+                merge_json(&mut merged, overlay);
+            }
+        }
+
+        let mut config: Self = if merged.is_null() {
+            Self::default()
         } else {
-            Ok(Self::default())
+            serde_json::from_value(merged).map_err(AppError::Json)?
+        };
+        config.project_dir = project_dir.map(Path::to_path_buf);
+        Ok(config)
+    }
+
+    /// Apply the named profile, or `default_profile` when `name` is `None`.
+    ///
+    /// An unknown `name` is an error; an unknown `default_profile` (e.g. one
+    /// defined by a project that is not the current one) only warns, so the
+    /// setting can still be fixed with `rr config`.
+    pub fn with_profile(self, name: Option<&str>) -> Result<Self> {
+        if let Some(name) = name {
+            return self.apply_profile(name);
+        }
+        match self.default_profile.clone() {
+            None => Ok(self),
+            Some(name) if self.profiles.contains_key(&name) => self.apply_profile(&name),
+            Some(name) => {
+                display::print_warning(&format!(
+                    "Ignoring unknown default profile '{name}'; run `rr config unset default_profile` to clear it"
+                ));
+                Ok(self)
+            }
         }
     }
 
+    fn apply_profile(self, name: &str) -> Result<Self> {
+        let name = name.to_string();
+        let overrides = self
+            .profiles
+            .get(&name)
+            .ok_or_else(|| unknown_profile(&name))?
+            .as_object()
+            .ok_or_else(|| AppError::Config(format!("Profile '{name}' must be a JSON object")))?
+            .clone();
+
+        let mut value = serde_json::to_value(&self)?;
+        if let serde_json::Value::Object(base) = &mut value {
+            for (key, v) in overrides {
+                if key != "profiles" && key != "default_profile" {
+                    base.insert(key, v);
+                }
+            }
+        }
+
+        let mut applied: Self = serde_json::from_value(value).map_err(|e| {
+            AppError::Config(format!("Invalid profile '{name}': {e}"))
+        })?;
+        applied.active_profile = Some(name);
+        applied.project_dir    = self.project_dir;
+        Ok(applied)
+    }
+
+    /// Walk up from `start` looking for a `.rustrest/` directory.
+    pub fn find_project_dir(start: &Path) -> Option<PathBuf> {
+        start
+            .ancestors()
+            .map(|dir| dir.join(PROJECT_DIR_NAME))
+            .find(|candidate| candidate.is_dir())
+    }
+
+    /// Collections directory of the discovered project, if any.
+    pub fn project_collections_dir(&self) -> Option<PathBuf> {
+        self.project_dir.as_ref().map(|dir| dir.join("collections"))
+    }
+
     pub fn save(&self) -> Result<()> {
         let path = Self::config_path();
         if let Some(parent) = path.parent() {
@@ -60,6 +180,96 @@ impl AppConfig {
         Ok(())
     }
 
+    /// Read a setting by dotted key, e.g. `timeout_secs` or
+    /// `default_headers.X-Api-Key`. Returns `None` for unset optional values.
+    pub fn get_key(&self, key: &str) -> Result<Option<String>> {
+        if let Some(name) = header_key(key)? {
+            return Ok(self.default_headers.get(name).cloned());
+        }
+        Ok(match key {
+            "default_headers"  => Some(serde_json::to_string_pretty(&self.default_headers)?),
+            "base_url"         => self.base_url.clone(),
+            "timeout_secs"     => Some(self.timeout_secs.to_string()),
+            "follow_redirects" => Some(self.follow_redirects.to_string()),
+            "max_redirects"    => Some(self.max_redirects.to_string()),
+            "max_history"      => Some(self.max_history.to_string()),
+            "collections_dir"  => Some(self.collections_dir.display().to_string()),
+            "default_profile"  => self.default_profile.clone(),
+            "redact.headers"    => Some(self.redact.headers.join(",")),
+            "redact.body_paths" => Some(self.redact.body_paths.join(",")),
+            _                  => return Err(unknown_key(key)),
+        })
+    }
+
+    /// Set a setting by dotted key, parsing `value` to the field's type.
+    pub fn set_key(&mut self, key: &str, value: &str) -> Result<()> {
+        if let Some(name) = header_key(key)? {
+            self.default_headers.insert(name.to_string(), value.to_string());
+            return Ok(());
+        }
+        match key {
+            "base_url" => {
+                url::Url::parse(value).map_err(|e| invalid_value(key, value, &e.to_string()))?;
+                self.base_url = Some(value.to_string());
+            }
+            "timeout_secs" => {
+                self.timeout_secs = value
+                    .parse()
+                    .map_err(|_| invalid_value(key, value, "expected a whole number of seconds"))?;
+            }
+            "follow_redirects" => self.follow_redirects = parse_bool(key, value)?,
+            "max_redirects" => {
+                self.max_redirects = value
+                    .parse()
+                    .map_err(|_| invalid_value(key, value, "expected a non-negative integer"))?;
+            }
+            "max_history" => {
+                self.max_history = value
+                    .parse()
+                    .map_err(|_| invalid_value(key, value, "expected a non-negative integer"))?;
+            }
+            "collections_dir" => self.collections_dir = PathBuf::from(value),
+            "redact.headers"    => self.redact.headers    = parse_list(value),
+            "redact.body_paths" => self.redact.body_paths = parse_list(value),
+            "default_profile" => {
+                if !self.profiles.contains_key(value) {
+                    return Err(unknown_profile(value));
+                }
+                self.default_profile = Some(value.to_string());
+            }
+            "default_headers" => {
+                return Err(AppError::Config(
+                    "Set individual headers with 'default_headers.<Name>'".into(),
+                ))
+            }
+            _ => return Err(unknown_key(key)),
+        }
+        Ok(())
+    }
+
+    /// Reset a setting to its default, or remove a single default header.
+    pub fn unset_key(&mut self, key: &str) -> Result<()> {
+        if let Some(name) = header_key(key)? {
+            self.default_headers.remove(name);
+            return Ok(());
+        }
+        let defaults = Self::default();
+        match key {
+            "default_headers"  => self.default_headers  = defaults.default_headers,
+            "base_url"         => self.base_url         = defaults.base_url,
+            "timeout_secs"     => self.timeout_secs     = defaults.timeout_secs,
+            "follow_redirects" => self.follow_redirects = defaults.follow_redirects,
+            "max_redirects"    => self.max_redirects    = defaults.max_redirects,
+            "max_history"      => self.max_history      = defaults.max_history,
+            "collections_dir"  => self.collections_dir  = defaults.collections_dir,
+            "default_profile"  => self.default_profile  = defaults.default_profile,
+            "redact.headers"    => self.redact.headers    = defaults.redact.headers,
+            "redact.body_paths" => self.redact.body_paths = defaults.redact.body_paths,
+            _                  => return Err(unknown_key(key)),
+        }
+        Ok(())
+    }
+
     pub fn config_dir() -> PathBuf {
         config_dir()
             .unwrap_or_else(|| PathBuf::from("."))
@@ -70,6 +280,10 @@ impl AppConfig {
         Self::config_dir().join("history.json")
     }
 
+    pub fn environments_dir() -> PathBuf {
+        Self::config_dir().join("environments")
+    }
+
     fn config_path() -> PathBuf {
         Self::config_dir().join("config.json")
     }
@@ -78,3 +292,98 @@ impl AppConfig {
         Self::config_dir().join("collections")
     }
 }
+
+// ── file helpers ──────────────────────────────────────────────────────────────
+
+/// Write `contents` readable by the current user only, for files holding
+/// credentials such as the OAuth2 token cache and the secrets vault.
+#[cfg(unix)]
+pub(crate) fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)?;
+    // `mode` only applies on creation; tighten files written by older versions.
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    file.write_all(contents)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub(crate) fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    std::fs::write(path, contents)?;
+    Ok(())
+}
+
+// ── layering helpers ──────────────────────────────────────────────────────────
+
+fn read_json(path: &Path) -> Result<Option<serde_json::Value>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content).map(Some).map_err(AppError::Json)
+}
+
+/// Recursively merge `overlay` into `base`; overlay values win.
+fn merge_json(base: &mut serde_json::Value, overlay: serde_json::Value) {
+    use serde_json::Value;
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                merge_json(base.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+// ── key helpers ───────────────────────────────────────────────────────────────
+
+/// Split `default_headers.<Name>` keys, returning the header name.
+fn header_key(key: &str) -> Result<Option<&str>> {
+    match key.strip_prefix("default_headers.") {
+        Some("")   => Err(AppError::Config("Missing header name after 'default_headers.'".into())),
+        Some(name) => Ok(Some(name)),
+        None       => Ok(None),
+    }
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1"  => Ok(true),
+        "false" | "no" | "off" | "0" => Ok(false),
+        _ => Err(invalid_value(key, value, "expected true or false")),
+    }
+}
+
+/// Split a comma-separated list, dropping empty entries.
+fn parse_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn unknown_key(key: &str) -> AppError {
+    AppError::Config(format!(
+        "Unknown key '{key}' — expected one of base_url, timeout_secs, follow_redirects, \
+         max_redirects, max_history, collections_dir, default_profile, \
+         redact.headers, redact.body_paths, default_headers.<Name>"
+    ))
+}
+
+fn unknown_profile(name: &str) -> AppError {
+    AppError::Config(format!("Unknown profile '{name}'"))
+}
+
+fn invalid_value(key: &str, value: &str, reason: &str) -> AppError {
+    AppError::Config(format!("Invalid value '{value}' for '{key}': {reason}"))
+}
@@ -0,0 +1,195 @@
+use crate::error::{AppError, Result};
+use md5::Md5;
+use sha2::{Digest as _, Sha256};
+
+/// Hash function named by the challenge's `algorithm` parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Md5,
+    Md5Sess,
+    Sha256,
+    Sha256Sess,
+}
+
+impl Algorithm {
+    fn parse(name: &str) -> Result<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "MD5"          => Ok(Algorithm::Md5),
+            "MD5-SESS"     => Ok(Algorithm::Md5Sess),
+            "SHA-256"      => Ok(Algorithm::Sha256),
+            "SHA-256-SESS" => Ok(Algorithm::Sha256Sess),
+            other => Err(AppError::Other(format!("Unsupported digest algorithm '{other}'"))),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Algorithm::Md5        => "MD5",
+            Algorithm::Md5Sess    => "MD5-sess",
+            Algorithm::Sha256     => "SHA-256",
+            Algorithm::Sha256Sess => "SHA-256-sess",
+        }
+    }
+
+    fn hash(self, input: &str) -> String {
+        match self {
+            Algorithm::Md5 | Algorithm::Md5Sess       => hex(&Md5::digest(input.as_bytes())),
+            Algorithm::Sha256 | Algorithm::Sha256Sess => hex(&Sha256::digest(input.as_bytes())),
+        }
+    }
+
+    fn is_session(self) -> bool {
+        matches!(self, Algorithm::Md5Sess | Algorithm::Sha256Sess)
+    }
+}
+
+/// A parsed `WWW-Authenticate: Digest …` challenge (RFC 7616).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    pub realm:     String,
+    pub nonce:     String,
+    pub opaque:    Option<String>,
+    pub algorithm: Algorithm,
+    /// Whether the server offered `qop=auth`; legacy RFC 2069 servers omit qop.
+    pub qop_auth:  bool,
+}
+
+impl Challenge {
+    /// Parse the value of a `WWW-Authenticate` header.
+    pub fn parse(header: &str) -> Result<Self> {
+        // Auth schemes are case-insensitive (RFC 9110 §11.1).
+        let params = header
+            .trim()
+            .split_once(|c: char| c.is_ascii_whitespace())
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("Digest"))
+            .map(|(_, params)| params)
+            .ok_or_else(|| AppError::Other(format!("Not a Digest challenge: {header}")))?;
+        let params = parse_params(params);
+        let get = |name: &str| {
+            params
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.clone())
+        };
+
+        let qop_auth = match get("qop") {
+            Some(qop) => {
+                if !qop.split(',').any(|q| q.trim().eq_ignore_ascii_case("auth")) {
+                    return Err(AppError::Other(format!("Unsupported digest qop '{qop}'")));
+                }
+                true
+            }
+            None => false,
+        };
+
+        Ok(Self {
+            realm:     get("realm").unwrap_or_default(),
+            nonce:     get("nonce").ok_or_else(|| AppError::Other("Digest challenge has no nonce".into()))?,
+            opaque:    get("opaque"),
+            algorithm: get("algorithm").map(|a| Algorithm::parse(&a)).transpose()?.unwrap_or(Algorithm::Md5),
+            qop_auth,
+        })
+    }
+
+    /// Build the `Authorization` header value for one request. `nc` is the
+    /// number of requests sent with this nonce, starting at 1.
+    pub fn authorization(
+        &self,
+        username: &str,
+        password: &str,
+        method: &str,
+        uri: &str,
+        nc: u32,
+        cnonce: &str,
+    ) -> String {
+        let alg = self.algorithm;
+        let mut ha1 = alg.hash(&format!("{username}:{}:{password}", self.realm));
+        if alg.is_session() {
+            ha1 = alg.hash(&format!("{ha1}:{}:{cnonce}", self.nonce));
+        }
+        let ha2 = alg.hash(&format!("{method}:{uri}"));
+        let nc  = format!("{nc:08x}");
+
+        let response = if self.qop_auth {
+            alg.hash(&format!("{ha1}:{}:{nc}:{cnonce}:auth:{ha2}", self.nonce))
+        } else {
+            alg.hash(&format!("{ha1}:{}:{ha2}", self.nonce))
+        };
+
+        let mut header = format!(
+            r#"Digest username="{username}", realm="{}", nonce="{}", uri="{uri}", algorithm={}, response="{response}""#,
+            self.realm,
+            self.nonce,
+            alg.as_str(),
+        );
+        if self.qop_auth {
+            header.push_str(&format!(r#", qop=auth, nc={nc}, cnonce="{cnonce}""#));
+        }
+        if let Some(opaque) = &self.opaque {
+            header.push_str(&format!(r#", opaque="{opaque}""#));
+        }
+        header
+    }
+}
+
+/// A fresh client nonce.
+pub fn cnonce() -> String {
+    let seed = format!(
+        "{}:{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default()
+    );
+    Algorithm::Md5.hash(&seed)[..16].to_string()
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+/// Split `key=value, key="quoted, value"` pairs.
+fn parse_params(input: &str) -> Vec<(String, String)> {
+    let mut out  = Vec::new();
+    let mut rest = input.trim();
+
+    while !rest.is_empty() {
+        let Some(eq) = rest.find('=') else { break };
+        let key = rest[..eq].trim().to_string();
+        rest = rest[eq + 1..].trim_start();
+
+        let value;
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let mut buf    = String::new();
+            let mut chars  = quoted.char_indices();
+            let mut end    = quoted.len();
+            while let Some((i, c)) = chars.next() {
+                match c {
+                    '\\' => {
+                        if let Some((_, next)) = chars.next() {
+                            buf.push(next);
+                        }
+                    }
+                    '"' => {
+                        end = i + 1;
+                        break;
+                    }
+                    _ => buf.push(c),
+                }
+            }
+            value = buf;
+            rest  = &quoted[end..];
+        } else {
+            let end = rest.find(',').unwrap_or(rest.len());
+            value = rest[..end].trim().to_string();
+            rest  = &rest[end..];
+        }
+
+        out.push((key, value));
+        rest = rest.trim_start().trim_start_matches(',').trim_start();
+    }
+    out
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
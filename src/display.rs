@@ -1,7 +1,19 @@
+use crate::format;
 use colored::Colorize;
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
 
 /// Print the HTTP status line, colour-coded by class.
 pub fn print_status_line(status: u16, reason: &str, elapsed_ms: u128) {
+    println!("{}", status_line(status, reason, elapsed_ms));
+}
+
+/// Print the status line to stderr, keeping stdout for the raw body.
+pub fn eprint_status_line(status: u16, reason: &str, elapsed_ms: u128) {
+    eprintln!("{}", status_line(status, reason, elapsed_ms));
+}
+
+fn status_line(status: u16, reason: &str, elapsed_ms: u128) -> String {
     let label = format!("{status} {reason}");
     let coloured = match status {
         200..=299 => label.green().bold(),
@@ -10,7 +22,7 @@ pub fn print_status_line(status: u16, reason: &str, elapsed_ms: u128) {
         500..=599 => label.bright_red().bold(),
         _         => label.white().bold(),
     };
-    println!("{coloured}  {}", format!("({elapsed_ms}ms)").dimmed());
+    format!("{coloured}  {}", format!("({elapsed_ms}ms)").dimmed())
 }
 
 /// Print response headers (verbose mode).
@@ -43,49 +55,63 @@ pub fn print_request_headers(headers: &[(String, String)]) {
     }
 }
 
-/// Pretty-print a response body, formatting JSON when detected.
+/// Pretty-print a response body with the formatter registered for its
+/// content type, printing it verbatim when none applies or it is malformed.
 pub fn print_pretty_body(body: &str, content_type: &str) {
-    let is_json = content_type.contains("application/json")
-        || content_type.contains("text/json");
-
-    if is_json {
-        match serde_json::from_str::<serde_json::Value>(body) {
-            Ok(v) => {
-                let pretty = serde_json::to_string_pretty(&v)
-                    .unwrap_or_else(|_| body.to_string());
-                println!("{}", colorize_json(&pretty));
-            }
-            Err(_) => println!("{body}"),
-        }
-    } else {
-        println!("{body}");
+    match format::pretty(body, content_type) {
+        Some(pretty) => println!("{pretty}"),
+        None         => println!("{body}"),
+    }
+}
+
+/// Print a summary of a binary body followed by a hexdump, paged through
+/// `$PAGER` (default `less -R`) when it would not fit on the terminal.
+pub fn print_binary_body(body: &[u8], content_type: &str) {
+    let summary = format!("[binary data: {} bytes, {content_type}]", body.len());
+    println!("{}", summary.dimmed());
+
+    let dump = hexdump(body);
+    let rows = dump.lines().count();
+    let fits = crossterm::terminal::size().map_or(true, |(_, height)| rows < height as usize);
+    if !std::io::stdout().is_terminal() || fits || !page(&dump) {
+        print!("{dump}");
     }
 }
 
-/// Minimal JSON syntax colouring (keys cyan, strings yellow).
-fn colorize_json(json: &str) -> String {
-    let mut out = String::with_capacity(json.len() * 2);
-    for line in json.lines() {
-        let trimmed = line.trim_start();
-        let indent  = &line[..line.len() - trimmed.len()];
-
-        // Key–value line: `"key": ...`
-        if trimmed.starts_with('"') {
-            if let Some(colon_pos) = trimmed.find("\": ") {
-                let key   = &trimmed[..=colon_pos + 1]; // includes `": `
-                let value = &trimmed[colon_pos + 3..];  // rest of line
-                let coloured_value = if value.starts_with('"') {
-                    value.yellow().to_string()
-                } else {
-                    value.to_string()
-                };
-                out.push_str(&format!("{indent}{}{coloured_value}\n", key.cyan()));
-                continue;
+/// Format `bytes` as `offset  hex hex …  |ascii|` rows of 16 bytes.
+pub fn hexdump(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 4 + 16);
+    for (row, chunk) in bytes.chunks(16).enumerate() {
+        let mut hex = String::with_capacity(49);
+        for (i, b) in chunk.iter().enumerate() {
+            if i == 8 {
+                hex.push(' ');
             }
+            hex.push_str(&format!("{b:02x} "));
         }
-        out.push_str(&format!("{line}\n"));
+        let ascii: String = chunk
+            .iter()
+            .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+            .collect();
+        out.push_str(&format!("{:08x}  {hex:<49} |{ascii}|\n", row * 16));
+    }
+    out
+}
+
+/// Write `text` through the user's pager; `false` if it could not be started.
+fn page(text: &str) -> bool {
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -R".to_string());
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else { return false };
+    let Ok(mut child) = Command::new(program).args(parts).stdin(Stdio::piped()).spawn() else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // The pager may exit early (e.g. `q`), closing the pipe; that is fine.
+        let _ = stdin.write_all(text.as_bytes());
     }
-    out.trim_end().to_string()
+    let _ = child.wait();
+    true
 }
 
 // ── status helpers ────────────────────────────────────────────────────────────
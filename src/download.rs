@@ -0,0 +1,194 @@
+use crate::cli::Cli;
+use crate::client::{self, HttpClient};
+use crate::config::AppConfig;
+use crate::display;
+use crate::error::{AppError, Result};
+use crate::history;
+use crate::request::HttpRequest;
+use futures_util::StreamExt;
+use reqwest::header::{CONTENT_DISPOSITION, CONTENT_LENGTH, RANGE};
+use reqwest::StatusCode;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tokio::io::AsyncWriteExt;
+
+/// Where and how to save a response body.
+#[derive(Debug, Clone, Default)]
+pub struct DownloadOptions {
+    /// Explicit target (`--output`). Otherwise the name comes from
+    /// `Content-Disposition` or the URL (`--download`).
+    pub output: Option<PathBuf>,
+    /// Continue a partial file with a `Range` request (`--resume`).
+    pub resume: bool,
+    /// Replace an existing file named by the response (`--force`).
+    pub force:  bool,
+}
+
+impl DownloadOptions {
+    /// Options from `--output` / `--download` / `--resume`, or `None` when
+    /// the response should be printed instead.
+    pub fn from_cli(cli: &Cli) -> Option<Self> {
+        (cli.output.is_some() || cli.download).then(|| Self {
+            output: cli.output.clone(),
+            resume: cli.resume,
+            force:  cli.force,
+        })
+    }
+}
+
+/// Stream the response to `req` to disk and return the file written.
+pub async fn download(
+    http: &HttpClient,
+    req: &HttpRequest,
+    opts: &DownloadOptions,
+    cli: &Cli,
+    config: &AppConfig,
+) -> Result<PathBuf> {
+    // Resuming needs the target before the response names it.
+    if opts.resume && opts.output.is_none() {
+        return Err(AppError::Other("Resuming a download needs an explicit --output file".into()));
+    }
+    let planned = opts.output.clone();
+    let offset  = match &planned {
+        Some(path) if opts.resume => std::fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+        _                         => 0,
+    };
+
+    let mut outgoing = req.clone();
+    outgoing.auth = outgoing.auth.map(|a| a.with_prompted_password()).transpose()?;
+    if offset > 0 {
+        outgoing.headers.insert(RANGE.to_string(), format!("bytes={offset}-"));
+    }
+
+    let start  = Instant::now();
+    let resp   = http.send(outgoing).await?;
+    let status = resp.status();
+
+    let target = planned.unwrap_or_else(|| {
+        let name = resp
+            .headers()
+            .get(CONTENT_DISPOSITION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(disposition_filename)
+            .unwrap_or_else(|| url_filename(&req.url));
+        PathBuf::from(name)
+    });
+
+    if status == StatusCode::RANGE_NOT_SATISFIABLE && offset > 0 {
+        history::record(req, status.as_u16(), start.elapsed().as_millis(), config)?;
+        if !cli.quiet {
+            display::print_info(&format!("{} is already complete", target.display()));
+        }
+        return Ok(target);
+    }
+    if !status.is_success() {
+        history::record(req, status.as_u16(), start.elapsed().as_millis(), config)?;
+        return Err(AppError::Other(format!(
+            "Download failed: {} {}",
+            status.as_u16(),
+            status.canonical_reason().unwrap_or("Unknown")
+        )));
+    }
+
+    // A 200 to a ranged request means the server ignored the range.
+    let append = status == StatusCode::PARTIAL_CONTENT && offset > 0;
+    // A name taken from the response must not clobber an existing file.
+    let keep   = opts.output.is_none() && !opts.resume && !opts.force;
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(!keep)
+        .create_new(keep)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(&target)
+        .await
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => AppError::Other(format!(
+                "'{}' already exists; use --force to overwrite it or --resume to continue it",
+                target.display()
+            )),
+            _ => AppError::Other(format!("Cannot write '{}': {e}", target.display())),
+        })?;
+
+    let already = if append { offset } else { 0 };
+    let length  = resp
+        .headers()
+        .get(CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<u64>().ok());
+    let bar = http.shows_progress().then(|| {
+        let bar = client::transfer_bar(length.map(|l| l + already), "Downloading");
+        bar.set_position(already);
+        bar
+    });
+
+    let mut written = 0u64;
+    let mut stream  = resp.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk).await?;
+        written += chunk.len() as u64;
+        if let Some(bar) = &bar {
+            bar.inc(chunk.len() as u64);
+        }
+    }
+    file.flush().await?;
+    if let Some(bar) = bar {
+        bar.finish_and_clear();
+    }
+
+    let elapsed = start.elapsed().as_millis();
+    history::record(req, status.as_u16(), elapsed, config)?;
+    if !cli.quiet {
+        display::print_status_line(status.as_u16(), status.canonical_reason().unwrap_or("Unknown"), elapsed);
+        let verb = if append { "Appended" } else { "Saved" };
+        display::print_success(&format!("{verb} {written} bytes to {}", target.display()));
+    }
+    Ok(target)
+}
+
+/// Extract a safe file name from a `Content-Disposition` header, preferring
+/// the RFC 5987 `filename*` form.
+pub fn disposition_filename(header: &str) -> Option<String> {
+    let params: Vec<(&str, &str)> = header
+        .split(';')
+        .skip(1)
+        .filter_map(|p| p.split_once('='))
+        .map(|(k, v)| (k.trim(), v.trim()))
+        .collect();
+
+    let extended = params
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("filename*"))
+        .and_then(|(_, v)| v.split_once("''"))
+        .map(|(_, encoded)| percent_decode(encoded));
+    let plain = || {
+        params
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case("filename"))
+            .map(|(_, v)| v.trim_matches('"').to_string())
+    };
+    extended.or_else(plain).and_then(|name| safe_filename(&name))
+}
+
+/// Last path segment of `url`, or `download` when there is none.
+pub fn url_filename(url: &str) -> String {
+    url::Url::parse(url)
+        .ok()
+        .and_then(|u| u.path_segments()?.next_back().map(percent_decode))
+        .and_then(|name| safe_filename(&name))
+        .unwrap_or_else(|| "download".to_string())
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+/// Keep only the final component so a server cannot write outside the
+/// working directory.
+fn safe_filename(name: &str) -> Option<String> {
+    let base = Path::new(name.rsplit(['/', '\\']).next()?).to_string_lossy().into_owned();
+    (!base.is_empty() && base != "." && base != "..").then_some(base)
+}
+
+fn percent_decode(input: &str) -> String {
+    percent_encoding::percent_decode_str(input).decode_utf8_lossy().into_owned()
+}
@@ -0,0 +1,149 @@
+use crate::config::AppConfig;
+use crate::error::{AppError, Result};
+use crate::request::{self, HttpRequest, MultipartField, RequestBody};
+use crate::vault;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::PathBuf;
+
+/// Variable scope used for `{{var}}` expansion.
+pub type Variables = HashMap<String, String>;
+
+/// A named set of variables, stored at
+/// `~/.config/rustrest/environments/<name>.json`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Environment {
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub variables:   Variables,
+}
+
+impl Environment {
+    pub fn load(name: &str) -> Result<Self> {
+        let path = environment_path(name);
+        if !path.exists() {
+            return Err(AppError::EnvironmentNotFound { name: name.to_string() });
+        }
+        let raw = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&raw).map_err(AppError::Json)
+    }
+}
+
+// ── public API ────────────────────────────────────────────────────────────────
+
+/// Load the variables of the environment selected with `--env`, if any.
+pub fn load_selected(name: Option<&str>) -> Result<Variables> {
+    match name {
+        Some(n) => Ok(Environment::load(n)?.variables),
+        None    => Ok(Variables::new()),
+    }
+}
+
+/// Replace every `{{name}}` placeholder in `input` with its value from `vars`.
+/// `{{secret:name}}` references are left for the vault to resolve at send time.
+pub fn substitute(input: &str, vars: &Variables) -> Result<String> {
+    let mut out  = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some((span, name)) = next_placeholder(rest) {
+        if name.starts_with(vault::SECRET_PREFIX) {
+            out.push_str(&rest[..span.end]);
+        } else {
+            let value = vars
+                .get(name)
+                .ok_or_else(|| AppError::UnresolvedVariable { name: name.to_string() })?;
+            out.push_str(&rest[..span.start]);
+            out.push_str(value);
+        }
+        rest = &rest[span.end..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Find the first `{{name}}` placeholder in `input`, returning its byte range
+/// (braces included) and the name with surrounding whitespace trimmed.
+pub fn next_placeholder(input: &str) -> Option<(Range<usize>, &str)> {
+    let start = input.find("{{")?;
+    let len   = input[start + 2..].find("}}")?;
+    Some((start..start + len + 4, input[start + 2..start + 2 + len].trim()))
+}
+
+/// Return a copy of `req` with placeholders expanded in the URL, headers,
+/// query parameters, body and auth. The URL is resolved against `base_url` after
+/// expansion, since templated URLs are not validated when first built.
+pub fn expand_request(req: &HttpRequest, vars: &Variables, base_url: Option<&str>) -> Result<HttpRequest> {
+    let mut expanded = map_strings(req, &|s| substitute(s, vars))?;
+    expanded.url = request::resolve_url(&expanded.url, base_url)?;
+    Ok(expanded)
+}
+
+/// Return a copy of `req` with `f` applied to the URL, headers, query
+/// parameters, body and auth.
+pub fn map_strings(req: &HttpRequest, f: &dyn Fn(&str) -> Result<String>) -> Result<HttpRequest> {
+    let body = match &req.body {
+        None                          => None,
+        Some(RequestBody::Json(v))    => Some(RequestBody::Json(map_json(v, f)?)),
+        Some(RequestBody::Form(map))  => Some(RequestBody::Form(map_pairs(map, f)?)),
+        Some(RequestBody::Text(s))    => Some(RequestBody::Text(f(s)?)),
+        Some(RequestBody::File(path)) => Some(RequestBody::File(f(path)?)),
+        Some(RequestBody::Multipart(fields)) => Some(RequestBody::Multipart(
+            fields
+                .iter()
+                .map(|field| {
+                    Ok(MultipartField {
+                        name:         f(&field.name)?,
+                        value:        field.value.as_deref().map(f).transpose()?,
+                        file:         field.file.as_deref().map(f).transpose()?,
+                        content_type: field.content_type.as_deref().map(f).transpose()?,
+                        filename:     field.filename.as_deref().map(f).transpose()?,
+                    })
+                })
+                .collect::<Result<_>>()?,
+        )),
+    };
+
+    let auth = match &req.auth {
+        Some(a) => Some(a.try_map_strings(f)?),
+        None    => None,
+    };
+
+    Ok(HttpRequest {
+        method:  req.method.clone(),
+        url:     f(&req.url)?,
+        headers: map_pairs(&req.headers, f)?,
+        query:   map_pairs(&req.query, f)?,
+        body,
+        auth,
+    })
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+fn environment_path(name: &str) -> PathBuf {
+    AppConfig::environments_dir().join(format!("{name}.json"))
+}
+
+fn map_pairs(map: &HashMap<String, String>, f: &dyn Fn(&str) -> Result<String>) -> Result<HashMap<String, String>> {
+    map.iter()
+        .map(|(k, v)| Ok((f(k)?, f(v)?)))
+        .collect()
+}
+
+fn map_json(value: &serde_json::Value, f: &dyn Fn(&str) -> Result<String>) -> Result<serde_json::Value> {
+    use serde_json::Value;
+    Ok(match value {
+        Value::String(s) => Value::String(f(s)?),
+        Value::Array(items) => Value::Array(
+            items.iter().map(|v| map_json(v, f)).collect::<Result<_>>()?,
+        ),
+        Value::Object(obj) => Value::Object(
+            obj.iter()
+                .map(|(k, v)| Ok((f(k)?, map_json(v, f)?)))
+                .collect::<Result<_>>()?,
+        ),
+        other => other.clone(),
+    })
+}
@@ -17,6 +17,33 @@ pub enum AppError {
     #[error("Collection '{name}' not found")]
     CollectionNotFound { name: String },
 
+    #[error("Environment '{name}' not found")]
+    EnvironmentNotFound { name: String },
+
+    #[error("Unresolved variable '{name}' — define it in the active environment (--env)")]
+    UnresolvedVariable { name: String },
+
+    #[error("Invalid JSON path '{path}': {reason}")]
+    InvalidJsonPath { path: String, reason: String },
+
+    #[error("Filter error: {0}")]
+    Filter(String),
+
+    #[error("Capture '{name}' failed: {reason}")]
+    Capture { name: String, reason: String },
+
+    #[error("OAuth2 error: {0}")]
+    OAuth(String),
+
+    #[error("Request signing failed: {0}")]
+    Signing(String),
+
+    #[error("Secrets vault: {0}")]
+    Vault(String),
+
+    #[error("{failed} assertion(s) failed")]
+    AssertionsFailed { failed: usize },
+
     #[error("Invalid header '{header}' — expected 'Key: Value'")]
     InvalidHeader { header: String },
 
@@ -27,5 +54,16 @@ pub enum AppError {
     Other(String),
 }
 
+impl AppError {
+    /// Process exit code for this error. Assertion failures get their own
+    /// code so CI pipelines can tell them apart from transport errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AppError::AssertionsFailed { .. } => 2,
+            _                                 => 1,
+        }
+    }
+}
+
 /// Crate-wide Result alias.
 pub type Result<T> = std::result::Result<T, AppError>;
@@ -0,0 +1,588 @@
+//! A jq-like filter language for response bodies.
+//!
+//! Supported: `.`, `.key`, `."odd key"`, `.[n]` (negative counts from the
+//! end), `.[a:b]`, `.[]` / `.*` wildcards, `|` pipes, `[ … ]` collection,
+//! comparisons (`== != < <= > >=`) joined by `and` / `or`, literals, and the
+//! functions `length`, `keys`, `not`, `select(f)` and `map(f)`.
+
+use crate::error::{AppError, Result};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// A parsed filter expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Filter {
+    Identity,
+    Field(Box<Filter>, String),
+    Index(Box<Filter>, i64),
+    Slice(Box<Filter>, Option<i64>, Option<i64>),
+    Iterate(Box<Filter>),
+    Pipe(Box<Filter>, Box<Filter>),
+    Collect(Box<Filter>),
+    Literal(Value),
+    Compare(Box<Filter>, CmpOp, Box<Filter>),
+    And(Box<Filter>, Box<Filter>),
+    Or(Box<Filter>, Box<Filter>),
+    Length,
+    Keys,
+    Not,
+    Select(Box<Filter>),
+    Map(Box<Filter>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Filter {
+    pub fn parse(expr: &str) -> Result<Self> {
+        let tokens = tokenize(expr)?;
+        let mut parser = Parser { expr, tokens, pos: 0 };
+        let filter = parser.pipe()?;
+        match parser.peek() {
+            None      => Ok(filter),
+            Some(tok) => Err(invalid(expr, &format!("unexpected {tok}"))),
+        }
+    }
+
+    /// Run the filter on `input`, producing zero or more outputs.
+    pub fn eval(&self, input: &Value) -> Result<Vec<Value>> {
+        Ok(match self {
+            Filter::Identity   => vec![input.clone()],
+            Filter::Literal(v) => vec![v.clone()],
+            Filter::Field(base, key) => base
+                .eval(input)?
+                .iter()
+                .map(|v| match v {
+                    Value::Object(map) => Ok(map.get(key).cloned().unwrap_or(Value::Null)),
+                    Value::Null        => Ok(Value::Null),
+                    other => Err(runtime(format!("cannot index {} with \"{key}\"", type_name(other)))),
+                })
+                .collect::<Result<_>>()?,
+            Filter::Index(base, idx) => base
+                .eval(input)?
+                .iter()
+                .map(|v| match v {
+                    Value::Array(items) => Ok(resolve_index(*idx, items.len())
+                        .and_then(|i| items.get(i).cloned())
+                        .unwrap_or(Value::Null)),
+                    Value::Null => Ok(Value::Null),
+                    other => Err(runtime(format!("cannot index {} with a number", type_name(other)))),
+                })
+                .collect::<Result<_>>()?,
+            Filter::Slice(base, from, to) => base
+                .eval(input)?
+                .iter()
+                .map(|v| slice(v, *from, *to))
+                .collect::<Result<_>>()?,
+            Filter::Iterate(base) => {
+                let mut out = Vec::new();
+                for v in base.eval(input)? {
+                    match v {
+                        Value::Array(items) => out.extend(items),
+                        Value::Object(map)  => out.extend(map.into_iter().map(|(_, v)| v)),
+                        other => return Err(runtime(format!("cannot iterate over {}", type_name(&other)))),
+                    }
+                }
+                out
+            }
+            Filter::Pipe(lhs, rhs) => {
+                let mut out = Vec::new();
+                for v in lhs.eval(input)? {
+                    out.extend(rhs.eval(&v)?);
+                }
+                out
+            }
+            Filter::Collect(inner) => vec![Value::Array(inner.eval(input)?)],
+            Filter::Compare(lhs, op, rhs) => {
+                let mut out = Vec::new();
+                for l in lhs.eval(input)? {
+                    for r in rhs.eval(input)? {
+                        let ord = compare(&l, &r);
+                        out.push(Value::Bool(match op {
+                            CmpOp::Eq => ord == Ordering::Equal,
+                            CmpOp::Ne => ord != Ordering::Equal,
+                            CmpOp::Lt => ord == Ordering::Less,
+                            CmpOp::Le => ord != Ordering::Greater,
+                            CmpOp::Gt => ord == Ordering::Greater,
+                            CmpOp::Ge => ord != Ordering::Less,
+                        }));
+                    }
+                }
+                out
+            }
+            Filter::And(lhs, rhs) => {
+                let l = lhs.eval(input)?.iter().all(truthy);
+                vec![Value::Bool(l && rhs.eval(input)?.iter().all(truthy))]
+            }
+            Filter::Or(lhs, rhs) => {
+                let l = lhs.eval(input)?.iter().any(truthy);
+                vec![Value::Bool(l || rhs.eval(input)?.iter().any(truthy))]
+            }
+            Filter::Length => vec![match input {
+                Value::Null        => Value::from(0),
+                Value::Bool(_)     => return Err(runtime("boolean has no length".into())),
+                Value::Number(n)   => match n.as_i64() {
+                    Some(i) => Value::from(i.unsigned_abs()),
+                    None    => Value::from(n.as_f64().unwrap_or_default().abs()),
+                },
+                Value::String(s)   => Value::from(s.chars().count()),
+                Value::Array(a)    => Value::from(a.len()),
+                Value::Object(m)   => Value::from(m.len()),
+            }],
+            Filter::Keys => vec![match input {
+                Value::Object(map) => {
+                    let mut keys: Vec<&String> = map.keys().collect();
+                    keys.sort();
+                    Value::Array(keys.into_iter().map(|k| Value::String(k.clone())).collect())
+                }
+                Value::Array(items) => Value::Array((0..items.len()).map(Value::from).collect()),
+                other => return Err(runtime(format!("{} has no keys", type_name(other)))),
+            }],
+            Filter::Not => vec![Value::Bool(!truthy(input))],
+            Filter::Select(cond) => {
+                if cond.eval(input)?.iter().any(truthy) {
+                    vec![input.clone()]
+                } else {
+                    Vec::new()
+                }
+            }
+            Filter::Map(inner) => {
+                let items = Filter::Iterate(Box::new(Filter::Identity)).eval(input)?;
+                let mut out = Vec::new();
+                for item in &items {
+                    out.extend(inner.eval(item)?);
+                }
+                vec![Value::Array(out)]
+            }
+        })
+    }
+}
+
+/// Parse `expr` and apply it to a JSON `body`.
+pub fn apply(body: &str, expr: &str) -> Result<Vec<Value>> {
+    let filter = Filter::parse(expr)?;
+    let input: Value = serde_json::from_str(body)
+        .map_err(|e| AppError::Filter(format!("response body is not JSON: {e}")))?;
+    filter.eval(&input)
+}
+
+// ── tokenizer ─────────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Dot,
+    Star,
+    Pipe,
+    Colon,
+    LBracket,
+    RBracket,
+    LParen,
+    RParen,
+    Op(CmpOp),
+    Ident(String),
+    Str(String),
+    Num(serde_json::Number),
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Dot       => write!(f, "'.'"),
+            Token::Star      => write!(f, "'*'"),
+            Token::Pipe      => write!(f, "'|'"),
+            Token::Colon     => write!(f, "':'"),
+            Token::LBracket  => write!(f, "'['"),
+            Token::RBracket  => write!(f, "']'"),
+            Token::LParen    => write!(f, "'('"),
+            Token::RParen    => write!(f, "')'"),
+            Token::Op(op)    => write!(f, "'{}'", match op {
+                CmpOp::Eq => "==",
+                CmpOp::Ne => "!=",
+                CmpOp::Lt => "<",
+                CmpOp::Le => "<=",
+                CmpOp::Gt => ">",
+                CmpOp::Ge => ">=",
+            }),
+            Token::Ident(w)  => write!(f, "'{w}'"),
+            Token::Str(s)    => write!(f, "\"{s}\""),
+            Token::Num(n)    => write!(f, "{n}"),
+        }
+    }
+}
+
+fn describe(tok: Option<&Token>) -> String {
+    tok.map_or_else(|| "end of expression".to_string(), Token::to_string)
+}
+
+/// Like jq, integer literals stay integers; only a fraction or exponent
+/// makes a float.
+fn number(raw: &str) -> Option<serde_json::Number> {
+    if !raw.contains(['.', 'e', 'E']) {
+        if let Ok(n) = raw.parse::<i64>() {
+            return Some(n.into());
+        }
+    }
+    raw.parse::<f64>().ok().and_then(serde_json::Number::from_f64)
+}
+
+fn tokenize(expr: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = expr.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let single = match c {
+            '.' => Some(Token::Dot),
+            '*' => Some(Token::Star),
+            '|' => Some(Token::Pipe),
+            ':' => Some(Token::Colon),
+            '[' => Some(Token::LBracket),
+            ']' => Some(Token::RBracket),
+            '(' => Some(Token::LParen),
+            ')' => Some(Token::RParen),
+            _   => None,
+        };
+        if let Some(tok) = single {
+            tokens.push(tok);
+            i += 1;
+            continue;
+        }
+        match (c, next) {
+            (c, _) if c.is_whitespace() => i += 1,
+            ('=', Some('=')) => { tokens.push(Token::Op(CmpOp::Eq)); i += 2 }
+            ('!', Some('=')) => { tokens.push(Token::Op(CmpOp::Ne)); i += 2 }
+            ('<', Some('=')) => { tokens.push(Token::Op(CmpOp::Le)); i += 2 }
+            ('>', Some('=')) => { tokens.push(Token::Op(CmpOp::Ge)); i += 2 }
+            ('<', _)         => { tokens.push(Token::Op(CmpOp::Lt)); i += 1 }
+            ('>', _)         => { tokens.push(Token::Op(CmpOp::Gt)); i += 1 }
+            ('"', _) => {
+                let mut s = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        None       => return Err(invalid(expr, "unterminated string")),
+                        Some('"')  => break,
+                        Some('\\') => {
+                            s.push(*chars.get(i + 1).ok_or_else(|| invalid(expr, "unterminated string"))?);
+                            i += 2;
+                        }
+                        Some(&ch)  => { s.push(ch); i += 1 }
+                    }
+                }
+                tokens.push(Token::Str(s));
+                i += 1;
+            }
+            (c, n) if c.is_ascii_digit() || (c == '-' && n.is_some_and(|n| n.is_ascii_digit())) => {
+                let start = i;
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                if i < chars.len() && matches!(chars[i], 'e' | 'E') {
+                    i += 1;
+                    if i < chars.len() && matches!(chars[i], '+' | '-') {
+                        i += 1;
+                    }
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+                let raw: String = chars[start..i].iter().collect();
+                let num = number(&raw).ok_or_else(|| invalid(expr, &format!("bad number '{raw}'")))?;
+                tokens.push(Token::Num(num));
+            }
+            (c, _) if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                tokens.push(Token::Ident(chars[start..i].iter().collect()));
+            }
+            (c, _) => return Err(invalid(expr, &format!("unexpected character '{c}'"))),
+        }
+    }
+    Ok(tokens)
+}
+
+// ── parser ────────────────────────────────────────────────────────────────────
+
+struct Parser<'a> {
+    expr:   &'a str,
+    tokens: Vec<Token>,
+    pos:    usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let tok = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        tok
+    }
+
+    fn expect(&mut self, want: Token) -> Result<()> {
+        match self.next() {
+            Some(tok) if tok == want => Ok(()),
+            other => Err(self.error(&format!("expected {want}, found {}", describe(other.as_ref())))),
+        }
+    }
+
+    fn eat_ident(&mut self, word: &str) -> bool {
+        if matches!(self.peek(), Some(Token::Ident(w)) if w == word) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self, reason: &str) -> AppError {
+        invalid(self.expr, reason)
+    }
+
+    fn pipe(&mut self) -> Result<Filter> {
+        let mut lhs = self.or()?;
+        while self.peek() == Some(&Token::Pipe) {
+            self.pos += 1;
+            lhs = Filter::Pipe(Box::new(lhs), Box::new(self.or()?));
+        }
+        Ok(lhs)
+    }
+
+    fn or(&mut self) -> Result<Filter> {
+        let mut lhs = self.and()?;
+        while self.eat_ident("or") {
+            lhs = Filter::Or(Box::new(lhs), Box::new(self.and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and(&mut self) -> Result<Filter> {
+        let mut lhs = self.comparison()?;
+        while self.eat_ident("and") {
+            lhs = Filter::And(Box::new(lhs), Box::new(self.comparison()?));
+        }
+        Ok(lhs)
+    }
+
+    fn comparison(&mut self) -> Result<Filter> {
+        let lhs = self.postfix()?;
+        if let Some(Token::Op(op)) = self.peek().cloned() {
+            self.pos += 1;
+            return Ok(Filter::Compare(Box::new(lhs), op, Box::new(self.postfix()?)));
+        }
+        Ok(lhs)
+    }
+
+    fn postfix(&mut self) -> Result<Filter> {
+        let mut filter = self.primary()?;
+        loop {
+            match self.peek() {
+                Some(Token::Dot) => {
+                    self.pos += 1;
+                    filter = match self.next() {
+                        Some(Token::Ident(key)) | Some(Token::Str(key)) => Filter::Field(Box::new(filter), key),
+                        Some(Token::Star)     => Filter::Iterate(Box::new(filter)),
+                        Some(Token::LBracket) => self.bracket(filter)?,
+                        other => return Err(self.error(&format!("expected a key after '.', found {}", describe(other.as_ref())))),
+                    };
+                }
+                Some(Token::LBracket) => {
+                    self.pos += 1;
+                    filter = self.bracket(filter)?;
+                }
+                _ => return Ok(filter),
+            }
+        }
+    }
+
+    /// Parse the inside of `[…]` after the opening bracket.
+    fn bracket(&mut self, base: Filter) -> Result<Filter> {
+        let base = Box::new(base);
+        let filter = match self.next() {
+            Some(Token::RBracket) => return Ok(Filter::Iterate(base)),
+            Some(Token::Star)     => Filter::Iterate(base),
+            Some(Token::Str(key)) => Filter::Field(base, key),
+            Some(Token::Colon)    => Filter::Slice(base, None, self.optional_int()?),
+            Some(Token::Num(n))   => {
+                let n = self.int(n)?;
+                if self.peek() == Some(&Token::Colon) {
+                    self.pos += 1;
+                    Filter::Slice(base, Some(n), self.optional_int()?)
+                } else {
+                    Filter::Index(base, n)
+                }
+            }
+            other => return Err(self.error(&format!("unexpected {} in '[…]'", describe(other.as_ref())))),
+        };
+        self.expect(Token::RBracket)?;
+        Ok(filter)
+    }
+
+    fn optional_int(&mut self) -> Result<Option<i64>> {
+        match self.peek().cloned() {
+            Some(Token::Num(n)) => {
+                self.pos += 1;
+                self.int(n).map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn int(&self, n: serde_json::Number) -> Result<i64> {
+        n.as_i64()
+            .or_else(|| n.as_f64().filter(|f| f.fract() == 0.0).map(|f| f as i64))
+            .ok_or_else(|| self.error(&format!("index {n} is not an integer")))
+    }
+
+    fn primary(&mut self) -> Result<Filter> {
+        match self.next() {
+            Some(Token::Dot) => match self.peek().cloned() {
+                Some(Token::Ident(key)) | Some(Token::Str(key)) => {
+                    self.pos += 1;
+                    Ok(Filter::Field(Box::new(Filter::Identity), key))
+                }
+                Some(Token::Star) => {
+                    self.pos += 1;
+                    Ok(Filter::Iterate(Box::new(Filter::Identity)))
+                }
+                _ => Ok(Filter::Identity),
+            },
+            Some(Token::Str(s)) => Ok(Filter::Literal(Value::String(s))),
+            Some(Token::Num(n)) => Ok(Filter::Literal(Value::Number(n))),
+            Some(Token::LParen) => {
+                let inner = self.pipe()?;
+                self.expect(Token::RParen)?;
+                Ok(inner)
+            }
+            Some(Token::LBracket) => {
+                if self.peek() == Some(&Token::RBracket) {
+                    self.pos += 1;
+                    return Ok(Filter::Literal(Value::Array(Vec::new())));
+                }
+                let inner = self.pipe()?;
+                self.expect(Token::RBracket)?;
+                Ok(Filter::Collect(Box::new(inner)))
+            }
+            Some(Token::Ident(name)) => match name.as_str() {
+                "true"   => Ok(Filter::Literal(Value::Bool(true))),
+                "false"  => Ok(Filter::Literal(Value::Bool(false))),
+                "null"   => Ok(Filter::Literal(Value::Null)),
+                "length" => Ok(Filter::Length),
+                "keys"   => Ok(Filter::Keys),
+                "not"    => Ok(Filter::Not),
+                "select" => Ok(Filter::Select(Box::new(self.argument()?))),
+                "map"    => Ok(Filter::Map(Box::new(self.argument()?))),
+                other    => Err(self.error(&format!("unknown function '{other}'"))),
+            },
+            other => Err(self.error(&format!("unexpected {}", describe(other.as_ref())))),
+        }
+    }
+
+    fn argument(&mut self) -> Result<Filter> {
+        self.expect(Token::LParen)?;
+        let arg = self.pipe()?;
+        self.expect(Token::RParen)?;
+        Ok(arg)
+    }
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+fn resolve_index(idx: i64, len: usize) -> Option<usize> {
+    let idx = if idx < 0 { len as i64 + idx } else { idx };
+    usize::try_from(idx).ok()
+}
+
+/// Clamp jq-style slice bounds to `0..=len`.
+fn bounds(from: Option<i64>, to: Option<i64>, len: usize) -> (usize, usize) {
+    let clamp = |i: i64| {
+        let i = if i < 0 { len as i64 + i } else { i };
+        i.clamp(0, len as i64) as usize
+    };
+    let start = from.map_or(0, clamp);
+    let end   = to.map_or(len, clamp);
+    (start, end.max(start))
+}
+
+fn slice(value: &Value, from: Option<i64>, to: Option<i64>) -> Result<Value> {
+    match value {
+        Value::Array(items) => {
+            let (start, end) = bounds(from, to, items.len());
+            Ok(Value::Array(items[start..end].to_vec()))
+        }
+        Value::String(s) => {
+            let chars: Vec<char> = s.chars().collect();
+            let (start, end) = bounds(from, to, chars.len());
+            Ok(Value::String(chars[start..end].iter().collect()))
+        }
+        Value::Null => Ok(Value::Null),
+        other => Err(runtime(format!("cannot slice {}", type_name(other)))),
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    !matches!(value, Value::Null | Value::Bool(false))
+}
+
+/// jq ordering: null < false < true < numbers < strings < arrays < objects.
+fn compare(a: &Value, b: &Value) -> Ordering {
+    fn rank(v: &Value) -> u8 {
+        match v {
+            Value::Null        => 0,
+            Value::Bool(false) => 1,
+            Value::Bool(true)  => 2,
+            Value::Number(_)   => 3,
+            Value::String(_)   => 4,
+            Value::Array(_)    => 5,
+            Value::Object(_)   => 6,
+        }
+    }
+    match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x
+            .as_f64()
+            .partial_cmp(&y.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(x), Value::String(y)) => x.cmp(y),
+        (Value::Array(x), Value::Array(y)) => x
+            .iter()
+            .zip(y)
+            .map(|(l, r)| compare(l, r))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or_else(|| x.len().cmp(&y.len())),
+        (Value::Object(_), Value::Object(_)) => {
+            if a == b { Ordering::Equal } else { a.to_string().cmp(&b.to_string()) }
+        }
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null      => "null",
+        Value::Bool(_)   => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_)  => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn invalid(expr: &str, reason: &str) -> AppError {
+    AppError::Filter(format!("invalid expression '{expr}': {reason}"))
+}
+
+fn runtime(reason: String) -> AppError {
+    AppError::Filter(reason)
+}
@@ -0,0 +1,459 @@
+//! Pretty-printers for response bodies, keyed on `Content-Type`.
+//!
+//! Each [`Formatter`] claims a family of media types and returns `None` when
+//! the body does not parse, in which case the caller prints it verbatim.
+
+use crate::highlight;
+use colored::Colorize;
+
+/// A pretty-printer for one family of media types.
+pub struct Formatter {
+    pub name: &'static str,
+    matches:  fn(&str) -> bool,
+    format:   fn(&str) -> Option<String>,
+}
+
+/// All formatters, tried in order against the media type.
+pub const FORMATTERS: &[Formatter] = &[
+    Formatter { name: "json", matches: is_json, format: format_json },
+    Formatter { name: "html", matches: is_html, format: format_html },
+    Formatter { name: "xml",  matches: is_xml,  format: format_xml },
+    Formatter { name: "yaml", matches: is_yaml, format: format_yaml },
+    Formatter { name: "form", matches: is_form, format: format_form },
+    Formatter { name: "csv",  matches: is_csv,  format: format_csv },
+];
+
+/// The formatter registered for `content_type`, if any.
+pub fn formatter_for(content_type: &str) -> Option<&'static Formatter> {
+    let mime = mime_essence(content_type);
+    FORMATTERS.iter().find(|f| (f.matches)(&mime))
+}
+
+/// Format `body` for `content_type`; `None` if no formatter applies or the
+/// body is malformed.
+pub fn pretty(body: &str, content_type: &str) -> Option<String> {
+    formatter_for(content_type).and_then(|f| (f.format)(body))
+}
+
+// ── media types ───────────────────────────────────────────────────────────────
+
+/// `Text/XML; charset=utf-8` → `text/xml`.
+fn mime_essence(content_type: &str) -> String {
+    content_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase()
+}
+
+fn is_json(mime: &str) -> bool {
+    matches!(mime, "application/json" | "text/json") || mime.ends_with("+json")
+}
+
+fn is_html(mime: &str) -> bool {
+    mime == "text/html"
+}
+
+fn is_xml(mime: &str) -> bool {
+    matches!(mime, "application/xml" | "text/xml") || mime.ends_with("+xml")
+}
+
+fn is_yaml(mime: &str) -> bool {
+    matches!(mime, "application/yaml" | "application/x-yaml" | "text/yaml" | "text/x-yaml") || mime.ends_with("+yaml")
+}
+
+fn is_form(mime: &str) -> bool {
+    mime == "application/x-www-form-urlencoded"
+}
+
+fn is_csv(mime: &str) -> bool {
+    mime == "text/csv"
+}
+
+// ── JSON ──────────────────────────────────────────────────────────────────────
+
+fn format_json(body: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    let pretty = serde_json::to_string_pretty(&value).ok()?;
+    Some(highlight::json(&pretty))
+}
+
+// ── XML / HTML ────────────────────────────────────────────────────────────────
+
+const INDENT: &str = "  ";
+
+/// HTML elements that never have a closing tag.
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source", "track", "wbr",
+];
+
+/// HTML elements whose content is printed untouched.
+const RAW_ELEMENTS: [&str; 4] = ["script", "style", "pre", "textarea"];
+
+#[derive(Debug, PartialEq)]
+enum Node<'a> {
+    Open { name: String, raw: &'a str },
+    Close { name: String, raw: &'a str },
+    Empty(&'a str),
+    /// Comments, processing instructions, doctypes and CDATA.
+    Other(&'a str),
+    Text(&'a str),
+    /// Content of a [`RAW_ELEMENTS`] element.
+    Raw(&'a str),
+}
+
+fn format_xml(body: &str) -> Option<String> {
+    format_markup(body, false)
+}
+
+fn format_html(body: &str) -> Option<String> {
+    format_markup(body, true)
+}
+
+/// Re-indent markup one element per line. XML must be well formed; HTML
+/// tolerates void elements, unclosed tags and stray closing tags.
+fn format_markup(body: &str, html: bool) -> Option<String> {
+    let nodes = markup_nodes(body, html)?;
+    if !nodes.iter().any(|n| matches!(n, Node::Open { .. } | Node::Empty(_))) {
+        return None;
+    }
+
+    let mut out   = Vec::new();
+    let mut stack: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < nodes.len() {
+        let indent = INDENT.repeat(stack.len());
+        match &nodes[i] {
+            Node::Open { name, raw } => {
+                // `<a>text</a>` and `<a></a>` stay on one line.
+                match (nodes.get(i + 1), nodes.get(i + 2)) {
+                    (Some(Node::Text(text)), Some(Node::Close { name: close, raw: end })) if close == name => {
+                        out.push(format!("{indent}{}{}{}", color_tag(raw), text_content(text, html), color_tag(end)));
+                        i += 3;
+                        continue;
+                    }
+                    (Some(Node::Close { name: close, raw: end }), _) if close == name => {
+                        out.push(format!("{indent}{}{}", color_tag(raw), color_tag(end)));
+                        i += 2;
+                        continue;
+                    }
+                    _ => {}
+                }
+                out.push(format!("{indent}{}", color_tag(raw)));
+                if !(html && VOID_ELEMENTS.contains(&name.as_str())) {
+                    stack.push(name.clone());
+                }
+            }
+            Node::Close { name, raw } => {
+                match stack.iter().rposition(|open| open == name) {
+                    Some(pos) if html || pos + 1 == stack.len() => stack.truncate(pos),
+                    _ if html => {}
+                    _ => return None,
+                }
+                out.push(format!("{}{}", INDENT.repeat(stack.len()), color_tag(raw)));
+            }
+            Node::Empty(raw) => out.push(format!("{indent}{}", color_tag(raw))),
+            Node::Other(raw) => out.push(format!("{indent}{}", raw.dimmed())),
+            Node::Text(text) => {
+                let text = text_content(text, html);
+                if !text.is_empty() {
+                    out.push(format!("{indent}{text}"));
+                }
+            }
+            Node::Raw(raw) => {
+                let raw = raw.trim_matches(['\n', '\r']);
+                if !raw.trim().is_empty() {
+                    out.push(raw.trim_end().to_string());
+                }
+            }
+        }
+        i += 1;
+    }
+
+    if !html && !stack.is_empty() {
+        return None;
+    }
+    Some(out.join("\n"))
+}
+
+fn markup_nodes(src: &str, html: bool) -> Option<Vec<Node<'_>>> {
+    let mut nodes = Vec::new();
+    let mut i = 0;
+    while i < src.len() {
+        let rest = &src[i..];
+        if !rest.starts_with('<') {
+            let end = rest.find('<').unwrap_or(rest.len());
+            nodes.push(Node::Text(&rest[..end]));
+            i += end;
+            continue;
+        }
+
+        let delimited = [("<!--", "-->"), ("<![CDATA[", "]]>"), ("<?", "?>"), ("<!", ">")]
+            .iter()
+            .find(|(open, _)| rest.starts_with(open));
+        if let Some((open, close)) = delimited {
+            let end = rest[open.len()..].find(close)? + open.len() + close.len();
+            nodes.push(Node::Other(&rest[..end]));
+            i += end;
+            continue;
+        }
+
+        let closing = rest.starts_with("</");
+        let name_at = if closing { 2 } else { 1 };
+        if !rest[name_at..].starts_with(|c: char| c.is_alphabetic()) {
+            // A bare `<` in text: tolerated in HTML, malformed in XML.
+            if !html {
+                return None;
+            }
+            nodes.push(Node::Text(&rest[..1]));
+            i += 1;
+            continue;
+        }
+
+        let end = tag_end(rest)?;
+        let raw = &rest[..end];
+        let name: String = raw[name_at..]
+            .chars()
+            .take_while(|c| !c.is_whitespace() && *c != '/' && *c != '>')
+            .collect();
+        let name = if html { name.to_ascii_lowercase() } else { name };
+        i += end;
+
+        if closing {
+            nodes.push(Node::Close { name, raw });
+        } else if raw.ends_with("/>") {
+            nodes.push(Node::Empty(raw));
+        } else if html && RAW_ELEMENTS.contains(&name.as_str()) {
+            let close   = format!("</{name}");
+            let content = &src[i..];
+            let len     = content.to_ascii_lowercase().find(&close).unwrap_or(content.len());
+            nodes.push(Node::Open { name, raw });
+            if len > 0 {
+                nodes.push(Node::Raw(&content[..len]));
+            }
+            i += len;
+        } else {
+            nodes.push(Node::Open { name, raw });
+        }
+    }
+    Some(nodes)
+}
+
+/// Byte length of the tag starting at `rest`, honouring quoted attributes.
+fn tag_end(rest: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in rest.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q    => quote = None,
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '>')                => return Some(i + 1),
+            _                          => {}
+        }
+    }
+    None
+}
+
+fn text_content(text: &str, html: bool) -> String {
+    if html {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    } else {
+        text.trim().to_string()
+    }
+}
+
+/// Colour a tag: element name blue, attribute names cyan, values yellow.
+fn color_tag(raw: &str) -> String {
+    let open_len = if raw.starts_with("</") { 2 } else { 1 };
+    let close_len = if raw.ends_with("/>") { 2 } else { 1 };
+    let inner = &raw[open_len..raw.len() - close_len];
+
+    let name_len = inner.find(char::is_whitespace).unwrap_or(inner.len());
+    let mut out = format!("{}{}", &raw[..open_len], inner[..name_len].bright_blue());
+
+    let mut rest = &inner[name_len..];
+    while !rest.is_empty() {
+        let ws = rest.len() - rest.trim_start().len();
+        out.push_str(&rest[..ws]);
+        rest = &rest[ws..];
+
+        let attr_len = rest.find(|c: char| c == '=' || c.is_whitespace()).unwrap_or(rest.len());
+        out.push_str(&rest[..attr_len].cyan().to_string());
+        rest = &rest[attr_len..];
+
+        if let Some(value) = rest.strip_prefix('=') {
+            out.push('=');
+            let value_len = match value.chars().next() {
+                Some(q @ ('"' | '\'')) => value[1..].find(q).map_or(value.len(), |e| e + 2),
+                _ => value.find(char::is_whitespace).unwrap_or(value.len()),
+            };
+            out.push_str(&value[..value_len].yellow().to_string());
+            rest = &value[value_len..];
+        }
+    }
+    out.push_str(&raw[raw.len() - close_len..]);
+    out
+}
+
+// ── YAML ──────────────────────────────────────────────────────────────────────
+
+/// Colour YAML line by line; indentation is already meaningful, so it is kept.
+fn format_yaml(body: &str) -> Option<String> {
+    let lines: Vec<String> = body.lines().map(yaml_line).collect();
+    Some(lines.join("\n").trim_end().to_string())
+}
+
+fn yaml_line(line: &str) -> String {
+    let trimmed = line.trim_start();
+    let indent  = &line[..line.len() - trimmed.len()];
+    if trimmed.starts_with('#') || trimmed == "---" || trimmed == "..." {
+        return format!("{indent}{}", trimmed.dimmed());
+    }
+
+    let mut out  = indent.to_string();
+    let mut rest = trimmed;
+    while let Some(item) = rest.strip_prefix("- ").or(if rest == "-" { Some("") } else { None }) {
+        out.push_str(&format!("{} ", "-".dimmed()));
+        rest = item.trim_start();
+    }
+
+    let (content, comment) = split_comment(rest);
+    match yaml_key(content) {
+        Some(colon) => {
+            let (key, value) = (&content[..colon], &content[colon + 1..]);
+            out.push_str(&format!("{}:", key.cyan()));
+            let ws = value.len() - value.trim_start().len();
+            out.push_str(&value[..ws]);
+            out.push_str(&yaml_scalar(&value[ws..]));
+        }
+        None => out.push_str(&yaml_scalar(content)),
+    }
+    if !comment.is_empty() {
+        out.push_str(&comment.dimmed().to_string());
+    }
+    out
+}
+
+/// Position of the `:` ending a mapping key, ignoring quoted text.
+fn yaml_key(s: &str) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q     => quote = None,
+            (None, '"') | (None, '\'') if i == 0 => quote = Some(c),
+            (None, ':') if s[i + 1..].is_empty() || s[i + 1..].starts_with(' ') => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split off a trailing ` # comment` outside quotes.
+fn split_comment(s: &str) -> (&str, &str) {
+    let mut quote = None;
+    for (i, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q     => quote = None,
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '#') if i > 0 && s[..i].ends_with(' ') => {
+                let start = s[..i].trim_end().len();
+                return (&s[..start], &s[start..]);
+            }
+            _ => {}
+        }
+    }
+    (s, "")
+}
+
+fn yaml_scalar(value: &str) -> String {
+    match value {
+        "" => String::new(),
+        "true" | "false" | "yes" | "no" | "on" | "off" | "True" | "False" => value.blue().to_string(),
+        "null" | "~" | "Null" | "NULL" => value.dimmed().to_string(),
+        v if v.starts_with('"') || v.starts_with('\'') => v.yellow().to_string(),
+        v if v.parse::<f64>().is_ok() => v.magenta().to_string(),
+        v => v.to_string(),
+    }
+}
+
+// ── form-urlencoded ───────────────────────────────────────────────────────────
+
+/// Decode `a=1&b=two` into aligned `key = value` lines.
+fn format_form(body: &str) -> Option<String> {
+    let pairs: Vec<(String, String)> = url::form_urlencoded::parse(body.trim().as_bytes())
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+    if pairs.is_empty() {
+        return None;
+    }
+    let width = pairs.iter().map(|(k, _)| k.chars().count()).max().unwrap_or(0);
+    let lines: Vec<String> = pairs
+        .iter()
+        .map(|(k, v)| format!("{} = {}", format!("{k:<width$}").cyan(), v.yellow()))
+        .collect();
+    Some(lines.join("\n"))
+}
+
+// ── CSV ───────────────────────────────────────────────────────────────────────
+
+/// Render CSV as an aligned table with a highlighted header row.
+fn format_csv(body: &str) -> Option<String> {
+    let rows = parse_csv(body)?;
+    let columns = rows.iter().map(Vec::len).max()?;
+    let widths: Vec<usize> = (0..columns)
+        .map(|c| rows.iter().filter_map(|r| r.get(c)).map(|f| f.chars().count()).max().unwrap_or(0))
+        .collect();
+
+    let separator = format!(" {} ", "│".dimmed());
+    let render = |row: &[String], header: bool| -> String {
+        let cells: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(c, w)| {
+                let text = row.get(c).map_or("", String::as_str);
+                let cell = if c + 1 == columns { text.to_string() } else { format!("{text:<w$}") };
+                if header { cell.cyan().bold().to_string() } else { cell }
+            })
+            .collect();
+        cells.join(&separator).trim_end().to_string()
+    };
+
+    let mut out = vec![render(&rows[0], true)];
+    let rule: Vec<String> = widths.iter().map(|w| "─".repeat(*w)).collect();
+    out.push(rule.join("─┼─").dimmed().to_string());
+    out.extend(rows[1..].iter().map(|r| render(r, false)));
+    Some(out.join("\n"))
+}
+
+/// RFC 4180 fields; `None` on an unterminated quote. Embedded newlines are
+/// shown as spaces so each record stays on one line.
+fn parse_csv(body: &str) -> Option<Vec<Vec<String>>> {
+    let mut rows  = Vec::new();
+    let mut row   = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = body.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            (true, '"')            => quoted = false,
+            (true, '\n')           => field.push(' '),
+            (true, c)              => field.push(c),
+            (false, '"') if field.is_empty() => quoted = true,
+            (false, ',')           => row.push(std::mem::take(&mut field)),
+            (false, '\r')          => {}
+            (false, '\n')          => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (false, c)             => field.push(c),
+        }
+    }
+    if quoted {
+        return None;
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    (!rows.is_empty()).then_some(rows)
+}
@@ -0,0 +1,104 @@
+//! Token-based JSON syntax highlighting, shared by the CLI and the TUI.
+//!
+//! [`tokens`] splits text into spans that concatenate back to the input, so
+//! each front end only has to map a [`TokenKind`] to its own styling.
+//! Malformed input is still tokenized; unknown characters become punctuation.
+
+use colored::Colorize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// An object key, including its quotes.
+    Key,
+    String,
+    Number,
+    Boolean,
+    Null,
+    /// Brackets, `:`, `,` and anything unrecognised.
+    Punctuation,
+    Whitespace,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub kind: TokenKind,
+    pub text: &'a str,
+}
+
+/// Whether colour output is enabled (honours `NO_COLOR` and `CLICOLOR*`).
+pub fn enabled() -> bool {
+    colored::control::SHOULD_COLORIZE.should_colorize()
+}
+
+/// Colour JSON text for the terminal: keys cyan, strings yellow, numbers
+/// magenta, booleans blue and `null` dimmed.
+pub fn json(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() * 2);
+    for token in tokens(text) {
+        let painted = match token.kind {
+            TokenKind::Key     => token.text.cyan(),
+            TokenKind::String  => token.text.yellow(),
+            TokenKind::Number  => token.text.magenta(),
+            TokenKind::Boolean => token.text.blue(),
+            TokenKind::Null    => token.text.dimmed(),
+            TokenKind::Punctuation | TokenKind::Whitespace => {
+                out.push_str(token.text);
+                continue;
+            }
+        };
+        out.push_str(&painted.to_string());
+    }
+    out
+}
+
+/// Split JSON text into highlighted spans.
+pub fn tokens(text: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let (kind, len) = match c {
+            c if c.is_whitespace() => (TokenKind::Whitespace, span_len(rest, char::is_whitespace)),
+            '"' => {
+                let len = string_len(rest);
+                let after = rest[len..].trim_start();
+                (if after.starts_with(':') { TokenKind::Key } else { TokenKind::String }, len)
+            }
+            '-' | '0'..='9' => (TokenKind::Number, span_len(rest, |c| c.is_ascii_digit() || "+-.eE".contains(c))),
+            c if c.is_ascii_alphabetic() => {
+                let len = span_len(rest, |c| c.is_ascii_alphabetic());
+                let kind = match &rest[..len] {
+                    "true" | "false" => TokenKind::Boolean,
+                    "null"           => TokenKind::Null,
+                    _                => TokenKind::Punctuation,
+                };
+                (kind, len)
+            }
+            c => (TokenKind::Punctuation, c.len_utf8()),
+        };
+        tokens.push(Token { kind, text: &rest[..len] });
+        rest = &rest[len..];
+    }
+    tokens
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+fn span_len(s: &str, keep: impl Fn(char) -> bool) -> usize {
+    s.find(|c: char| !keep(c)).unwrap_or(s.len())
+}
+
+/// Byte length of the string literal at the start of `s`, closing quote
+/// included; an unterminated string runs to the end of the line.
+fn string_len(s: &str) -> usize {
+    let mut escaped = false;
+    for (i, c) in s.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\'         => escaped = true,
+            '"'          => return i + 1,
+            '\n'         => return i,
+            _            => {}
+        }
+    }
+    s.len()
+}
@@ -54,7 +54,7 @@ pub fn record(req: &HttpRequest, status: u16, elapsed_ms: u128, config: &AppConf
         HistoryEntry {
             id: next_id,
             timestamp: Utc::now(),
-            request: req.clone(),
+            request: config.redact.request(req),
             status,
             elapsed_ms,
         },
@@ -0,0 +1,120 @@
+use crate::error::{AppError, Result};
+use serde_json::Value;
+
+/// One step of a parsed path expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+/// Select a single value from `root`.
+///
+/// Accepts either a JSON pointer (`/data/items/0/id`) or a JSONPath subset
+/// (`$.data.items[0].id`, `$['odd key']`). Returns `Ok(None)` when the path is
+/// well-formed but does not match anything.
+pub fn select<'a>(root: &'a Value, expr: &str) -> Result<Option<&'a Value>> {
+    let mut current = root;
+    for seg in parse(expr)? {
+        let next = match (&seg, current) {
+            (Segment::Key(k), Value::Object(map))   => map.get(k),
+            (Segment::Index(i), Value::Array(arr))  => arr.get(*i),
+            (Segment::Index(i), Value::Object(map)) => map.get(&i.to_string()),
+            _                                       => None,
+        };
+        match next {
+            Some(v) => current = v,
+            None    => return Ok(None),
+        }
+    }
+    Ok(Some(current))
+}
+
+/// Mutable counterpart of [`select`].
+pub fn select_mut<'a>(root: &'a mut Value, expr: &str) -> Result<Option<&'a mut Value>> {
+    let mut current = root;
+    for seg in parse(expr)? {
+        let next = match (&seg, current) {
+            (Segment::Key(k), Value::Object(map))   => map.get_mut(k),
+            (Segment::Index(i), Value::Array(arr))  => arr.get_mut(*i),
+            (Segment::Index(i), Value::Object(map)) => map.get_mut(&i.to_string()),
+            _                                       => None,
+        };
+        match next {
+            Some(v) => current = v,
+            None    => return Ok(None),
+        }
+    }
+    Ok(Some(current))
+}
+
+/// Parse `expr` into path segments.
+pub fn parse(expr: &str) -> Result<Vec<Segment>> {
+    let expr = expr.trim();
+    if expr.is_empty() || expr.starts_with('/') {
+        return Ok(parse_pointer(expr));
+    }
+    let Some(rest) = expr.strip_prefix('$') else {
+        return Err(invalid(expr, "expected a JSON pointer ('/…') or a JSONPath ('$…')"));
+    };
+
+    let chars: Vec<char> = rest.chars().collect();
+    let mut segments = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '.' => {
+                let start = i + 1;
+                i = start;
+                while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                    i += 1;
+                }
+                if i == start {
+                    return Err(invalid(expr, "empty key after '.'"));
+                }
+                segments.push(Segment::Key(chars[start..i].iter().collect()));
+            }
+            '[' => {
+                let close = chars[i..]
+                    .iter()
+                    .position(|&c| c == ']')
+                    .map(|p| p + i)
+                    .ok_or_else(|| invalid(expr, "unclosed '['"))?;
+                let inner: String = chars[i + 1..close].iter().collect();
+                let inner = inner.trim();
+                let quoted = (inner.starts_with('\'') && inner.ends_with('\''))
+                    || (inner.starts_with('"') && inner.ends_with('"'));
+                if quoted && inner.len() >= 2 {
+                    segments.push(Segment::Key(inner[1..inner.len() - 1].to_string()));
+                } else {
+                    let idx = inner
+                        .parse::<usize>()
+                        .map_err(|_| invalid(expr, &format!("invalid index '{inner}'")))?;
+                    segments.push(Segment::Index(idx));
+                }
+                i = close + 1;
+            }
+            c => return Err(invalid(expr, &format!("unexpected character '{c}'"))),
+        }
+    }
+    Ok(segments)
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+fn parse_pointer(expr: &str) -> Vec<Segment> {
+    expr.split('/')
+        .skip(1)
+        .map(|raw| {
+            let key = raw.replace("~1", "/").replace("~0", "~");
+            match key.parse::<usize>() {
+                Ok(i) if !key.starts_with('0') || key == "0" => Segment::Index(i),
+                _ => Segment::Key(key),
+            }
+        })
+        .collect()
+}
+
+fn invalid(path: &str, reason: &str) -> AppError {
+    AppError::InvalidJsonPath { path: path.to_string(), reason: reason.to_string() }
+}
@@ -1,10 +1,29 @@
+pub mod assertion;
+pub mod auth;
+pub mod capture;
+pub mod charset;
 pub mod cli;
 pub mod client;
 pub mod collections;
 pub mod config;
+pub mod digest;
 pub mod display;
+pub mod download;
+pub mod environment;
 pub mod error;
+pub mod filter;
+pub mod format;
+pub mod highlight;
 pub mod history;
+pub mod jsonpath;
+pub mod oauth;
+pub mod redact;
+pub mod report;
 pub mod request;
 pub mod response;
+pub mod runner;
+pub mod settings;
+pub mod signature;
+pub mod sigv4;
 pub mod tui;
+pub mod vault;
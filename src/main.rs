@@ -2,21 +2,31 @@ use anyhow::Context;
 use clap::Parser;
 use rustrest::{
     cli::{Cli, Command, ConfigAction},
-    client, collections, config, display, history, request, response, tui,
+    charset, client, collections, config, display, download, environment, filter, history, request, settings, tui, vault,
+    auth::Auth,
+    error::AppError,
 };
-use std::time::Instant;
+use std::io::IsTerminal;
 
 #[tokio::main]
 async fn main() {
     if let Err(e) = run().await {
         display::print_error(&e.to_string());
-        std::process::exit(1);
+        let code = e.downcast_ref::<AppError>().map_or(1, AppError::exit_code);
+        std::process::exit(code);
     }
 }
 
 async fn run() -> anyhow::Result<()> {
     let cli = Cli::parse();
+    cli.validate().unwrap_or_else(|e| e.exit());
     let cfg = config::AppConfig::load().context("Failed to load configuration")?;
+    // `config` edits the files themselves, so it always sees them unprofiled.
+    let cfg = if matches!(cli.command, Command::Config { .. }) {
+        cfg
+    } else {
+        cfg.with_profile(cli.profile.as_deref())?
+    };
 
     match &cli.command {
         // ── HTTP commands ────────────────────────────────────────────────────
@@ -26,29 +36,32 @@ async fn run() -> anyhow::Result<()> {
         | Command::Patch { .. }
         | Command::Delete { .. }
         | Command::Head { .. } => {
-            let http = client::HttpClient::with_options(cli.timeout, true, cli.insecure)?;
+            // Reject a malformed --filter before the request is sent.
+            if let Some(expr) = &cli.filter {
+                filter::Filter::parse(expr)?;
+            }
+            let http = client::HttpClient::from_settings(&settings::Settings::resolve(&cli, &cfg))?
+                .with_progress(!cli.quiet && std::io::stderr().is_terminal())
+                .with_encoding(charset::from_cli(&cli)?);
+            let vars = environment::load_selected(cli.env.as_deref())?;
             let req  = request::HttpRequest::from_command(&cli.command, &cfg)?;
-
-            if cli.verbose {
-                display::print_request_line(&req.method.to_string(), &req.url);
-                let hdrs: Vec<_> = req.headers.iter()
-                    .map(|(k, v)| (k.clone(), v.clone()))
-                    .collect();
-                display::print_request_headers(&hdrs);
+            let mut req = environment::expand_request(&req, &vars, cfg.base_url.as_deref())?;
+            if let Some(a) = Auth::from_cli(&cli)? {
+                req.auth = Some(a);
+            }
+            match download::DownloadOptions::from_cli(&cli) {
+                Some(opts) => {
+                    download::download(&http, &req, &opts, &cli, &cfg).await?;
+                }
+                None => {
+                    client::execute(&http, &req, &cli, &cfg).await?;
+                }
             }
-
-            let start   = Instant::now();
-            let resp    = http.send(req.clone()).await?;
-            let elapsed = start.elapsed().as_millis();
-            let status  = resp.status().as_u16();
-
-            response::print_response(resp, &cli, elapsed).await?;
-            history::record(&req, status, elapsed, &cfg)?;
         }
 
         // ── collections ──────────────────────────────────────────────────────
         Command::Collection { action } => {
-            collections::handle_action(action, &cfg)?;
+            collections::handle_action(action, &cli, &cfg).await?;
         }
 
         // ── history ──────────────────────────────────────────────────────────
@@ -61,10 +74,31 @@ async fn run() -> anyhow::Result<()> {
             ConfigAction::Show => {
                 println!("{}", serde_json::to_string_pretty(&cfg)?);
             }
+            ConfigAction::Get { key } => match cfg.get_key(key)? {
+                Some(value) => println!("{value}"),
+                None        => display::print_info(&format!("'{key}' is not set")),
+            },
             ConfigAction::Set { key, value } => {
-                display::print_warning(&format!(
-                    "Setting '{key}' = '{value}' is not yet implemented"
-                ));
+                let mut updated = config::AppConfig::load_global()?;
+                if key == "default_profile" {
+                    check_global_profile(&updated, &cfg, value)?;
+                }
+                updated.set_key(key, value)?;
+                updated.save()?;
+                display::print_success(&format!("Set '{key}' = '{value}'"));
+            }
+            ConfigAction::Unset { key } => {
+                let mut updated = config::AppConfig::load_global()?;
+                updated.unset_key(key)?;
+                updated.save()?;
+                display::print_success(&format!("Unset '{key}'"));
+            }
+            ConfigAction::Use { profile } => {
+                let mut updated = config::AppConfig::load_global()?;
+                check_global_profile(&updated, &cfg, profile)?;
+                updated.set_key("default_profile", profile)?;
+                updated.save()?;
+                display::print_success(&format!("Now using profile '{profile}'"));
             }
             ConfigAction::Reset => {
                 config::AppConfig::default().save()?;
@@ -72,6 +106,11 @@ async fn run() -> anyhow::Result<()> {
             }
         },
 
+        // ── secrets ──────────────────────────────────────────────────────────
+        Command::Secret { action } => {
+            vault::handle_action(action)?;
+        }
+
         // ── TUI ──────────────────────────────────────────────────────────────
         Command::Tui => {
             tui::run().await?;
@@ -80,3 +119,15 @@ async fn run() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// `default_profile` is saved to the global file, so it must name a global
+/// profile; one defined only by the current project would be unknown
+/// everywhere else.
+fn check_global_profile(global: &config::AppConfig, layered: &config::AppConfig, name: &str) -> anyhow::Result<()> {
+    if !global.profiles.contains_key(name) && layered.profiles.contains_key(name) {
+        anyhow::bail!(AppError::Config(format!(
+            "Profile '{name}' is defined only in the project config and cannot be the global default"
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use crate::config::{self, AppConfig};
+use crate::error::{AppError, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Tokens expiring within this many seconds are refreshed early.
+const EXPIRY_MARGIN_SECS: i64 = 30;
+
+/// OAuth 2.0 settings for the `oauth2` auth type.
+///
+/// ```json
+/// "auth": {
+///   "type": "oauth2",
+///   "token_url": "https://auth.example.com/oauth/token",
+///   "client_id": "cli",
+///   "client_secret": "{{client_secret}}",
+///   "scopes": ["read", "write"],
+///   "grant_type": "client_credentials"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OAuth2Config {
+    pub token_url:     String,
+    pub client_id:     String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes:        Vec<String>,
+    #[serde(default)]
+    pub grant_type:    GrantType,
+    /// Resource-owner credentials for the `password` grant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username:      Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password:      Option<String>,
+    /// Initial refresh token for the `refresh_token` grant.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GrantType {
+    #[default]
+    ClientCredentials,
+    Password,
+    RefreshToken,
+}
+
+impl GrantType {
+    fn as_str(self) -> &'static str {
+        match self {
+            GrantType::ClientCredentials => "client_credentials",
+            GrantType::Password          => "password",
+            GrantType::RefreshToken      => "refresh_token",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedToken {
+    access_token:  String,
+    #[serde(default)]
+    expires_at:    Option<DateTime<Utc>>,
+    #[serde(default)]
+    refresh_token: Option<String>,
+}
+
+impl CachedToken {
+    fn is_fresh(&self) -> bool {
+        match self.expires_at {
+            Some(at) => at > Utc::now() + Duration::seconds(EXPIRY_MARGIN_SECS),
+            None     => true,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token:  String,
+    #[serde(default)]
+    expires_in:    Option<i64>,
+    #[serde(default)]
+    refresh_token: Option<String>,
+}
+
+/// Access tokens persisted between runs, keyed by endpoint, client and grant.
+#[derive(Debug, Clone)]
+pub struct TokenCache {
+    path: PathBuf,
+}
+
+impl Default for TokenCache {
+    fn default() -> Self {
+        Self::at(AppConfig::config_dir().join("oauth_tokens.json"))
+    }
+}
+
+impl TokenCache {
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Return a usable access token for `cfg`, from the cache when it has not
+    /// expired. `force_refresh` skips the cache, e.g. after a 401.
+    pub async fn access_token(
+        &self,
+        http: &reqwest::Client,
+        cfg: &OAuth2Config,
+        force_refresh: bool,
+    ) -> Result<String> {
+        let key    = cache_key(cfg);
+        let mut all = self.load()?;
+        let cached = all.get(&key).cloned();
+
+        if let Some(token) = cached.as_ref().filter(|t| !force_refresh && t.is_fresh()) {
+            return Ok(token.access_token.clone());
+        }
+
+        let refresh = cached
+            .as_ref()
+            .and_then(|t| t.refresh_token.clone())
+            .or_else(|| cfg.refresh_token.clone());
+
+        let token = match refresh {
+            Some(rt) => match request_token(http, cfg, GrantType::RefreshToken, Some(&rt)).await {
+                Ok(token) => token,
+                Err(e) if cfg.grant_type == GrantType::RefreshToken => return Err(e),
+                Err(_) => request_token(http, cfg, cfg.grant_type, None).await?,
+            },
+            None => request_token(http, cfg, cfg.grant_type, None).await?,
+        };
+
+        let access = token.access_token.clone();
+        all.insert(key, token);
+        self.save(&all)?;
+        Ok(access)
+    }
+
+    fn load(&self) -> Result<HashMap<String, CachedToken>> {
+        if !self.path.exists() {
+            return Ok(HashMap::new());
+        }
+        let raw = std::fs::read_to_string(&self.path)?;
+        // A corrupt cache only costs a token request.
+        Ok(serde_json::from_str(&raw).unwrap_or_default())
+    }
+
+    fn save(&self, tokens: &HashMap<String, CachedToken>) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        config::write_private(&self.path, serde_json::to_string_pretty(tokens)?.as_bytes())?;
+        Ok(())
+    }
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+fn cache_key(cfg: &OAuth2Config) -> String {
+    format!(
+        "{}|{}|{}|{}|{}",
+        cfg.token_url,
+        cfg.client_id,
+        cfg.grant_type.as_str(),
+        cfg.username.as_deref().unwrap_or(""),
+        cfg.scopes.join(" ")
+    )
+}
+
+async fn request_token(
+    http: &reqwest::Client,
+    cfg: &OAuth2Config,
+    grant: GrantType,
+    refresh_token: Option<&str>,
+) -> Result<CachedToken> {
+    let mut form: Vec<(&str, &str)> = vec![
+        ("grant_type", grant.as_str()),
+        ("client_id", &cfg.client_id),
+    ];
+    if let Some(secret) = &cfg.client_secret {
+        form.push(("client_secret", secret));
+    }
+    let scope = cfg.scopes.join(" ");
+    if !scope.is_empty() {
+        form.push(("scope", &scope));
+    }
+    match grant {
+        GrantType::ClientCredentials => {}
+        GrantType::Password => {
+            let username = cfg.username.as_deref().ok_or_else(|| missing("username"))?;
+            let password = cfg.password.as_deref().ok_or_else(|| missing("password"))?;
+            form.push(("username", username));
+            form.push(("password", password));
+        }
+        GrantType::RefreshToken => {
+            let rt = refresh_token.ok_or_else(|| missing("refresh_token"))?;
+            form.push(("refresh_token", rt));
+        }
+    }
+
+    let resp = http.post(&cfg.token_url).form(&form).send().await?;
+    let status = resp.status();
+    let body = resp.text().await?;
+    if !status.is_success() {
+        return Err(AppError::OAuth(format!("token endpoint returned {status}: {body}")));
+    }
+
+    let parsed: TokenResponse = serde_json::from_str(&body)
+        .map_err(|e| AppError::OAuth(format!("invalid token response: {e}")))?;
+    Ok(CachedToken {
+        access_token:  parsed.access_token,
+        expires_at:    parsed.expires_in.map(|secs| Utc::now() + Duration::seconds(secs)),
+        refresh_token: parsed.refresh_token.or_else(|| refresh_token.map(str::to_string)),
+    })
+}
+
+fn missing(field: &str) -> AppError {
+    AppError::OAuth(format!("'{field}' is required for this grant type"))
+}
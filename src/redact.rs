@@ -0,0 +1,166 @@
+use crate::auth::{Auth, REDACTED};
+use crate::jsonpath;
+use crate::request::{HttpRequest, MultipartField, RequestBody};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use url::Url;
+
+/// Values hidden from `history.json` and verbose output.
+///
+/// `body_paths` entries starting with `$` or `/` are JSON paths; bare names
+/// such as `password` match that key at any depth, and form fields by name.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Redaction {
+    pub headers:    Vec<String>,
+    pub body_paths: Vec<String>,
+}
+
+impl Default for Redaction {
+    fn default() -> Self {
+        Self {
+            headers: ["Authorization", "Proxy-Authorization", "Cookie", "Set-Cookie", "X-Api-Key"]
+                .map(String::from)
+                .to_vec(),
+            body_paths: ["password", "client_secret", "access_token", "refresh_token", "api_key"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+}
+
+impl Redaction {
+    pub fn is_sensitive_header(&self, name: &str) -> bool {
+        self.headers.iter().any(|h| h.eq_ignore_ascii_case(name))
+    }
+
+    /// Copy of `headers` with sensitive values masked.
+    pub fn headers<'a>(&self, headers: impl IntoIterator<Item = (&'a String, &'a String)>) -> Vec<(String, String)> {
+        headers
+            .into_iter()
+            .map(|(k, v)| {
+                let v = if self.is_sensitive_header(k) { REDACTED } else { v.as_str() };
+                (k.clone(), v.to_string())
+            })
+            .collect()
+    }
+
+    /// Copy of `req` that is safe to persist: sensitive headers, query
+    /// parameters, body fields and auth credentials are masked.
+    pub fn request(&self, req: &HttpRequest) -> HttpRequest {
+        let headers = self.headers(&req.headers).into_iter().collect();
+        let query = req
+            .query
+            .iter()
+            .map(|(k, v)| {
+                let v = if self.is_sensitive_field(k) { REDACTED.to_string() } else { v.clone() };
+                (k.clone(), v)
+            })
+            .collect();
+        let body = match &req.body {
+            Some(RequestBody::Text(text)) => Some(RequestBody::Text(self.text(text))),
+            Some(RequestBody::Json(v)) => {
+                let mut v = v.clone();
+                self.json(&mut v);
+                Some(RequestBody::Json(v))
+            }
+            Some(RequestBody::Form(map)) => Some(RequestBody::Form(
+                map.iter()
+                    .map(|(k, v)| {
+                        let v = if self.is_sensitive_field(k) { REDACTED.to_string() } else { v.clone() };
+                        (k.clone(), v)
+                    })
+                    .collect(),
+            )),
+            Some(RequestBody::Multipart(fields)) => Some(RequestBody::Multipart(
+                fields
+                    .iter()
+                    .map(|field| match &field.value {
+                        Some(_) if self.is_sensitive_field(&field.name) => MultipartField {
+                            value: Some(REDACTED.to_string()),
+                            ..field.clone()
+                        },
+                        _ => field.clone(),
+                    })
+                    .collect(),
+            )),
+            other => other.clone(),
+        };
+
+        HttpRequest {
+            url: self.url(&req.url),
+            headers,
+            query,
+            body,
+            auth: req.auth.as_ref().map(Auth::redacted),
+            ..req.clone()
+        }
+    }
+
+    /// Mask every configured path or field name in `value`.
+    pub fn json(&self, value: &mut Value) {
+        for rule in &self.body_paths {
+            if rule.starts_with('$') || rule.starts_with('/') {
+                // Invalid paths in config simply match nothing.
+                if let Ok(Some(target)) = jsonpath::select_mut(value, rule) {
+                    *target = Value::String(REDACTED.to_string());
+                }
+            } else {
+                mask_key(value, rule);
+            }
+        }
+    }
+
+    /// `text` with sensitive fields masked when it is a JSON document;
+    /// anything else is returned unchanged.
+    pub fn text(&self, text: &str) -> String {
+        let Ok(mut value) = serde_json::from_str::<Value>(text) else { return text.to_string() };
+        let original = value.clone();
+        self.json(&mut value);
+        if value == original {
+            text.to_string()
+        } else {
+            value.to_string()
+        }
+    }
+
+    /// `url` with sensitive query parameters masked.
+    pub fn url(&self, url: &str) -> String {
+        let Ok(mut parsed) = Url::parse(url) else { return url.to_string() };
+        if !parsed.query_pairs().any(|(k, _)| self.is_sensitive_field(&k)) {
+            return url.to_string();
+        }
+        let pairs: Vec<(String, String)> = parsed
+            .query_pairs()
+            .map(|(k, v)| {
+                let v = if self.is_sensitive_field(&k) { REDACTED.into() } else { v };
+                (k.into_owned(), v.into_owned())
+            })
+            .collect();
+        parsed.query_pairs_mut().clear().extend_pairs(pairs);
+        parsed.into()
+    }
+
+    /// Whether a form field, query key or bare JSON key is listed in `body_paths`.
+    pub fn is_sensitive_field(&self, name: &str) -> bool {
+        self.body_paths.iter().any(|p| p.eq_ignore_ascii_case(name))
+    }
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+fn mask_key(value: &mut Value, key: &str) {
+    match value {
+        Value::Object(map) => {
+            for (k, v) in map.iter_mut() {
+                if k.eq_ignore_ascii_case(key) {
+                    *v = Value::String(REDACTED.to_string());
+                } else {
+                    mask_key(v, key);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|v| mask_key(v, key)),
+        _ => {}
+    }
+}
@@ -0,0 +1,262 @@
+use crate::config::AppConfig;
+use crate::error::Result;
+use crate::redact::Redaction;
+use crate::request::{HttpRequest, RequestBody};
+use crate::response::ParsedResponse;
+use crate::runner::RequestOutcome;
+use chrono::Utc;
+use serde_json::json;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Junit,
+    Json,
+}
+
+impl std::fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReportFormat::Junit => write!(f, "JUnit"),
+            ReportFormat::Json  => write!(f, "JSON"),
+        }
+    }
+}
+
+/// A `--report FORMAT:PATH` argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportSpec {
+    pub format: ReportFormat,
+    pub path:   PathBuf,
+}
+
+impl FromStr for ReportSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (format, path) = s
+            .split_once(':')
+            .ok_or_else(|| format!("invalid report '{s}' — expected FORMAT:PATH"))?;
+        let format = match format.to_ascii_lowercase().as_str() {
+            "junit" => ReportFormat::Junit,
+            "json"  => ReportFormat::Json,
+            other   => return Err(format!("unknown report format '{other}' — expected junit or json")),
+        };
+        if path.is_empty() {
+            return Err(format!("invalid report '{s}' — missing output path"));
+        }
+        Ok(Self { format, path: PathBuf::from(path) })
+    }
+}
+
+// ── public API ────────────────────────────────────────────────────────────────
+
+/// Write the outcomes of a collection run to `spec.path`, masking secrets
+/// with the configured redaction rules.
+pub fn write(spec: &ReportSpec, collection: &str, outcomes: &[RequestOutcome], config: &AppConfig) -> Result<()> {
+    let content = match spec.format {
+        ReportFormat::Junit => junit(collection, outcomes, &config.redact),
+        ReportFormat::Json  => serde_json::to_string_pretty(&json_report(collection, outcomes, &config.redact))?,
+    };
+    if let Some(parent) = spec.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&spec.path, content)?;
+    Ok(())
+}
+
+/// Render a JUnit XML document with one `<testcase>` per request.
+pub fn junit(collection: &str, outcomes: &[RequestOutcome], redact: &Redaction) -> String {
+    let tests    = outcomes.len();
+    let errors   = outcomes.iter().filter(|o| is_error(o)).count();
+    let failures = outcomes.iter().filter(|o| !o.passed() && !is_error(o)).count();
+    let time     = seconds(outcomes.iter().map(|o| o.elapsed_ms).sum());
+    let name     = escape(collection);
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"rustrest\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\" time=\"{time}\">"
+    );
+    let _ = writeln!(
+        xml,
+        "  <testsuite name=\"{name}\" tests=\"{tests}\" failures=\"{failures}\" errors=\"{errors}\" time=\"{time}\" timestamp=\"{}\">",
+        Utc::now().format("%Y-%m-%dT%H:%M:%S")
+    );
+
+    for o in outcomes {
+        let _ = write!(
+            xml,
+            "    <testcase name=\"{}\" classname=\"{name}\" time=\"{}\"",
+            escape(&o.key),
+            seconds(o.elapsed_ms)
+        );
+        if o.passed() {
+            xml.push_str("/>\n");
+            continue;
+        }
+        xml.push_str(">\n");
+
+        if let Some(err) = &o.error {
+            let kind = if is_error(o) { "error" } else { "failure" };
+            let _ = writeln!(xml, "      <{kind} message=\"{}\" type=\"{kind}\"/>", escape(err));
+        }
+        if !o.failures.is_empty() {
+            let detail: Vec<String> = o.failures.iter().map(|f| f.to_string()).collect();
+            let _ = writeln!(
+                xml,
+                "      <failure message=\"{} assertion(s) failed\" type=\"assertion\">{}</failure>",
+                o.failures.len(),
+                escape(&detail.join("\n"))
+            );
+        }
+        let _ = writeln!(xml, "      <system-out>{}</system-out>", escape(&exchange_dump(o, redact)));
+        xml.push_str("    </testcase>\n");
+    }
+
+    xml.push_str("  </testsuite>\n</testsuites>\n");
+    xml
+}
+
+/// Build the JSON report document.
+pub fn json_report(collection: &str, outcomes: &[RequestOutcome], redact: &Redaction) -> serde_json::Value {
+    let passed = outcomes.iter().filter(|o| o.passed()).count();
+    let errors = outcomes.iter().filter(|o| is_error(o)).count();
+
+    let requests: Vec<_> = outcomes
+        .iter()
+        .map(|o| {
+            let mut entry = json!({
+                "name":       o.key,
+                "method":     o.method,
+                "url":        redact.url(o.request.as_ref().map_or(&o.url, |r| &r.url)),
+                "status":     o.status,
+                "elapsed_ms": o.elapsed_ms,
+                "passed":     o.passed(),
+                "error":      o.error,
+                "failures":   o.failures,
+            });
+            if !o.passed() {
+                entry["request"]  = json!(o.request.as_ref().map(|r| safe_request(r, redact)));
+                entry["response"] = o.response.as_ref().map_or(serde_json::Value::Null, |r| response_json(r, redact));
+            }
+            entry
+        })
+        .collect();
+
+    json!({
+        "collection": collection,
+        "timestamp":  Utc::now().to_rfc3339(),
+        "summary": {
+            "tests":    outcomes.len(),
+            "passed":   passed,
+            "failed":   outcomes.len() - passed - errors,
+            "errors":   errors,
+            "time_ms":  outcomes.iter().map(|o| o.elapsed_ms).sum::<u128>(),
+        },
+        "requests": requests,
+    })
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+/// A request that never produced a response (transport or expansion error).
+fn is_error(o: &RequestOutcome) -> bool {
+    o.status.is_none()
+}
+
+fn seconds(ms: u128) -> String {
+    format!("{:.3}", ms as f64 / 1000.0)
+}
+
+/// `req` with sensitive headers and body fields masked and auth dropped.
+fn safe_request(req: &HttpRequest, redact: &Redaction) -> HttpRequest {
+    HttpRequest { auth: None, ..redact.request(req) }
+}
+
+fn response_json(resp: &ParsedResponse, redact: &Redaction) -> serde_json::Value {
+    json!({
+        "status":      resp.status,
+        "status_text": resp.status_text,
+        "headers":     redact.headers(resp.headers.iter().map(|(k, v)| (k, v))),
+        "body":        response_body_text(resp, redact),
+    })
+}
+
+/// Response body for reports; binary payloads are summarised, not inlined,
+/// and sensitive fields of JSON bodies are masked.
+fn response_body_text(resp: &ParsedResponse, redact: &Redaction) -> String {
+    if resp.is_binary() {
+        format!("<{} bytes of binary data>", resp.size_bytes)
+    } else {
+        redact.text(&resp.text())
+    }
+}
+
+/// Human-readable request/response transcript for failed test cases.
+fn exchange_dump(o: &RequestOutcome, redact: &Redaction) -> String {
+    let mut out = String::new();
+    if let Some(req) = &o.request {
+        let req = safe_request(req, redact);
+        let _ = writeln!(out, "> {} {}", req.method, req.url);
+        for (k, v) in sorted(&req.headers) {
+            let _ = writeln!(out, "> {k}: {v}");
+        }
+        if let Some(body) = request_body_text(&req) {
+            let _ = writeln!(out, ">\n{body}");
+        }
+    }
+    if let Some(resp) = &o.response {
+        let _ = writeln!(out, "< {} {}", resp.status, resp.status_text);
+        for (k, v) in redact.headers(resp.headers.iter().map(|(k, v)| (k, v))) {
+            let _ = writeln!(out, "< {k}: {v}");
+        }
+        if !resp.body.is_empty() {
+            let _ = writeln!(out, "<\n{}", response_body_text(resp, redact));
+        }
+    }
+    out
+}
+
+fn sorted(map: &std::collections::HashMap<String, String>) -> Vec<(&String, &String)> {
+    let mut pairs: Vec<_> = map.iter().collect();
+    pairs.sort();
+    pairs
+}
+
+fn request_body_text(req: &HttpRequest) -> Option<String> {
+    match &req.body {
+        None                         => None,
+        Some(RequestBody::Json(v))   => serde_json::to_string_pretty(v).ok(),
+        Some(RequestBody::Text(s))   => Some(s.clone()),
+        Some(RequestBody::File(p))   => Some(format!("@{p}")),
+        Some(RequestBody::Multipart(fields)) => Some(
+            fields.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n"),
+        ),
+        Some(RequestBody::Form(map)) => Some(
+            sorted(map)
+                .into_iter()
+                .map(|(k, v)| format!("{k}={v}"))
+                .collect::<Vec<_>>()
+                .join("&"),
+        ),
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&'  => out.push_str("&amp;"),
+            '<'  => out.push_str("&lt;"),
+            '>'  => out.push_str("&gt;"),
+            '"'  => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c if (c as u32) < 0x20 && !matches!(c, '\n' | '\r' | '\t') => {}
+            c    => out.push(c),
+        }
+    }
+    out
+}
@@ -1,3 +1,4 @@
+use crate::auth::Auth;
 use crate::cli::Command;
 use crate::config::AppConfig;
 use crate::error::{AppError, Result};
@@ -12,6 +13,8 @@ pub struct HttpRequest {
     pub headers: HashMap<String, String>,
     pub query:   HashMap<String, String>,
     pub body:    Option<RequestBody>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth:    Option<Auth>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +48,79 @@ pub enum RequestBody {
     Form(HashMap<String, String>),
     Text(String),
     File(String),
+    Multipart(Vec<MultipartField>),
+}
+
+/// One part of a `multipart/form-data` body: either a text `value` or a
+/// `file` streamed from disk.
+///
+/// ```json
+/// "body": { "multipart": [
+///   { "name": "title", "value": "Holiday" },
+///   { "name": "avatar", "file": "photo.png", "content_type": "image/png" }
+/// ] }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MultipartField {
+    pub name:         String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value:        Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file:         Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    /// Defaults to the file's base name.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filename:     Option<String>,
+}
+
+impl MultipartField {
+    /// Parse the CLI form `name=value` or `name=@path`, optionally followed
+    /// by `;type=MIME` and `;filename=NAME`.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let (name, rest) = raw
+            .split_once('=')
+            .filter(|(name, _)| !name.is_empty())
+            .ok_or_else(|| AppError::Other(format!("Invalid multipart field '{raw}' — expected name=value or name=@file")))?;
+
+        let mut parts        = rest.split(';');
+        let mut value        = parts.next().unwrap_or_default().to_string();
+        let mut content_type = None;
+        let mut filename     = None;
+        for part in parts {
+            if let Some(t) = part.strip_prefix("type=") {
+                content_type = Some(t.to_string());
+            } else if let Some(f) = part.strip_prefix("filename=") {
+                filename = Some(f.to_string());
+            } else {
+                // Not a parameter: the value itself contained ';'.
+                value.push(';');
+                value.push_str(part);
+            }
+        }
+
+        let (value, file) = match value.strip_prefix('@') {
+            Some(path) => (None, Some(path.to_string())),
+            None       => (Some(value), None),
+        };
+        Ok(Self { name: name.to_string(), value, file, content_type, filename })
+    }
+}
+
+impl std::fmt::Display for MultipartField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.file, &self.value) {
+            (Some(path), _)  => write!(f, "{}=@{path}", self.name)?,
+            (None, value)    => write!(f, "{}={}", self.name, value.as_deref().unwrap_or_default())?,
+        }
+        if let Some(t) = &self.content_type {
+            write!(f, ";type={t}")?;
+        }
+        if let Some(name) = &self.filename {
+            write!(f, ";filename={name}")?;
+        }
+        Ok(())
+    }
 }
 
 impl HttpRequest {
@@ -57,22 +133,22 @@ impl HttpRequest {
                 req.apply_query(query)?;
                 Ok(req)
             }
-            Command::Post { url, headers, body, form } => {
+            Command::Post { url, headers, body, form, multipart } => {
                 let mut req = Self::new(HttpMethod::Post, url, config)?;
                 req.apply_headers(headers)?;
-                req.body = resolve_body(body, form)?;
+                req.body = resolve_body(body, form, multipart)?;
                 Ok(req)
             }
-            Command::Put { url, headers, body } => {
+            Command::Put { url, headers, body, multipart } => {
                 let mut req = Self::new(HttpMethod::Put, url, config)?;
                 req.apply_headers(headers)?;
-                req.body = resolve_body(body, &[])?;
+                req.body = resolve_body(body, &[], multipart)?;
                 Ok(req)
             }
-            Command::Patch { url, headers, body } => {
+            Command::Patch { url, headers, body, multipart } => {
                 let mut req = Self::new(HttpMethod::Patch, url, config)?;
                 req.apply_headers(headers)?;
-                req.body = resolve_body(body, &[])?;
+                req.body = resolve_body(body, &[], multipart)?;
                 Ok(req)
             }
             Command::Delete { url, headers } => {
@@ -96,6 +172,7 @@ impl HttpRequest {
             headers: config.default_headers.clone(),
             query: HashMap::new(),
             body: None,
+            auth: None,
         })
     }
 
@@ -120,7 +197,13 @@ impl HttpRequest {
 
 // ── helpers ──────────────────────────────────────────────────────────────────
 
-fn resolve_url(url: &str, base: Option<&str>) -> Result<String> {
+/// Normalise `url` into an absolute URL, joining relative paths onto `base`.
+/// URLs still containing `{{var}}` placeholders are returned untouched and
+/// resolved once the active environment has been applied.
+pub(crate) fn resolve_url(url: &str, base: Option<&str>) -> Result<String> {
+    if url.contains("{{") {
+        return Ok(url.to_string());
+    }
     if url.starts_with("http://") || url.starts_with("https://") {
         Url::parse(url)?;
         return Ok(url.to_string());
@@ -141,7 +224,11 @@ fn parse_header(raw: &str) -> Result<(String, String)> {
         .ok_or_else(|| AppError::InvalidHeader { header: raw.to_string() })
 }
 
-fn resolve_body(body: &Option<String>, form: &[String]) -> Result<Option<RequestBody>> {
+fn resolve_body(body: &Option<String>, form: &[String], multipart: &[String]) -> Result<Option<RequestBody>> {
+    if !multipart.is_empty() {
+        let fields = multipart.iter().map(|f| MultipartField::parse(f)).collect::<Result<_>>()?;
+        return Ok(Some(RequestBody::Multipart(fields)));
+    }
     if !form.is_empty() {
         let mut map = HashMap::new();
         for kv in form {
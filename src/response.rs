@@ -1,41 +1,109 @@
+use crate::charset::{Charset, CharsetSource};
 use crate::cli::{Cli, OutputFormat};
+use crate::charset;
 use crate::display;
 use crate::error::Result;
+use crate::filter;
+use encoding_rs::Encoding;
 use reqwest::Response;
+use std::borrow::Cow;
+use std::io::{IsTerminal, Write};
 
+#[derive(Debug, Clone)]
 pub struct ParsedResponse {
     pub status:       u16,
     pub status_text:  String,
     pub headers:      Vec<(String, String)>,
-    pub body:         String,
+    /// Raw body bytes, exactly as received.
+    pub body:         Vec<u8>,
     pub content_type: String,
     pub size_bytes:   usize,
     pub elapsed_ms:   u128,
+    /// Charset used by [`ParsedResponse::text`].
+    pub charset:      Charset,
+}
+
+impl ParsedResponse {
+    /// The body decoded with its detected charset; malformed sequences are
+    /// replaced.
+    pub fn text(&self) -> Cow<'_, str> {
+        self.charset.decode(&self.body)
+    }
+
+    /// A byte order mark or an explicit `--encoding` marks the body as text.
+    pub fn is_binary(&self) -> bool {
+        !matches!(self.charset.source, CharsetSource::Bom | CharsetSource::Override)
+            && is_binary(&self.content_type, &self.body)
+    }
 }
 
 /// Consume a raw `reqwest::Response`, print it according to CLI flags, and
 /// return the structured representation for further use (e.g. history).
 pub async fn print_response(resp: Response, cli: &Cli, elapsed_ms: u128) -> Result<ParsedResponse> {
-    let parsed = parse_response(resp, elapsed_ms).await?;
+    let parsed = parse_response(resp, elapsed_ms, charset::from_cli(cli)?).await?;
+    print_parsed(&parsed, cli)?;
+    Ok(parsed)
+}
 
+/// Print an already-parsed response according to CLI flags.
+pub fn print_parsed(parsed: &ParsedResponse, cli: &Cli) -> Result<()> {
     if !cli.quiet {
-        display::print_status_line(parsed.status, &parsed.status_text, elapsed_ms);
+        // Raw output may be piped, so only the body goes to stdout.
+        if cli.format == OutputFormat::Plain {
+            display::eprint_status_line(parsed.status, &parsed.status_text, parsed.elapsed_ms);
+        } else {
+            display::print_status_line(parsed.status, &parsed.status_text, parsed.elapsed_ms);
+        }
     }
 
     if cli.verbose && !cli.quiet {
         display::print_response_headers(&parsed.headers);
+        if !parsed.body.is_empty() && !parsed.is_binary() {
+            display::print_info(&format!("Charset: {}", parsed.charset));
+        }
     }
 
-    match cli.format {
-        OutputFormat::Pretty => display::print_pretty_body(&parsed.body, &parsed.content_type),
-        OutputFormat::Json   => println!("{}", parsed.body),
-        OutputFormat::Plain  => print!("{}", parsed.body),
+    if let Some(expr) = &cli.filter {
+        for value in filter::apply(&parsed.text(), expr)? {
+            match cli.format {
+                OutputFormat::Pretty => display::print_pretty_body(&value.to_string(), "application/json"),
+                OutputFormat::Json   => println!("{value}"),
+                OutputFormat::Plain  => match value {
+                    serde_json::Value::String(s) => println!("{s}"),
+                    other                        => println!("{other}"),
+                },
+            }
+        }
+        return Ok(());
     }
 
-    Ok(parsed)
+    let stdout = std::io::stdout();
+    match (cli.format, parsed.is_binary()) {
+        (OutputFormat::Pretty, false) => display::print_pretty_body(&parsed.text(), &parsed.content_type),
+        (OutputFormat::Json, false)   => println!("{}", parsed.text()),
+        (OutputFormat::Pretty, true)  => display::print_binary_body(&parsed.body, &parsed.content_type),
+        // Piped or redirected: pass the bytes through untouched.
+        (OutputFormat::Plain, _) | (OutputFormat::Json, true) if !stdout.is_terminal() => {
+            let mut out = stdout.lock();
+            out.write_all(&parsed.body)?;
+            out.flush()?;
+        }
+        (OutputFormat::Plain, false) => print!("{}", parsed.text()),
+        (_, true) => display::print_warning(&format!(
+            "{} bytes of binary data not printed to the terminal; redirect stdout or use --output",
+            parsed.size_bytes,
+        )),
+    }
+    Ok(())
 }
 
-pub async fn parse_response(resp: Response, elapsed_ms: u128) -> Result<ParsedResponse> {
+/// Parse `resp`, decoding text with `forced` instead of the detected charset
+/// when given.
+pub async fn parse_response(
+    resp: Response,
+    elapsed_ms: u128,
+    forced: Option<&'static Encoding>,
+) -> Result<ParsedResponse> {
     let status      = resp.status().as_u16();
     let status_text = resp.status().canonical_reason().unwrap_or("Unknown").to_string();
 
@@ -52,8 +120,82 @@ pub async fn parse_response(resp: Response, elapsed_ms: u128) -> Result<ParsedRe
         .map(|(k, v)| (k.to_string(), v.to_str().unwrap_or("").to_string()))
         .collect();
 
-    let body       = resp.text().await.map_err(crate::error::AppError::Http)?;
+    let body       = resp.bytes().await.map_err(crate::error::AppError::Http)?.to_vec();
     let size_bytes = body.len();
+    let charset    = Charset::detect(&content_type, &body, forced);
+
+    Ok(ParsedResponse { status, status_text, headers, body, content_type, size_bytes, elapsed_ms, charset })
+}
 
-    Ok(ParsedResponse { status, status_text, headers, body, content_type, size_bytes, elapsed_ms })
+// ── binary detection ──────────────────────────────────────────────────────────
+
+/// How much of the body is inspected when the content type is inconclusive.
+const SNIFF_LEN: usize = 8 * 1024;
+
+/// Decide whether a body should be treated as binary.
+///
+/// Well-known text and binary media types decide on their own; anything else
+/// (including a missing or `application/octet-stream` type) is sniffed for
+/// magic numbers, NUL bytes, invalid UTF-8 and control characters.
+pub fn is_binary(content_type: &str, body: &[u8]) -> bool {
+    if body.is_empty() {
+        return false;
+    }
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+
+    const TEXT_HINTS: [&str; 8] = ["json", "xml", "javascript", "ecmascript", "yaml", "csv", "x-www-form-urlencoded", "graphql"];
+    let declares_charset = content_type.to_ascii_lowercase().contains("charset=");
+    if declares_charset || mime.starts_with("text/") || TEXT_HINTS.iter().any(|h| mime.contains(h)) {
+        return false;
+    }
+    const BINARY_PREFIXES: [&str; 5] = ["image/", "audio/", "video/", "font/", "model/"];
+    const BINARY_TYPES: [&str; 10] = [
+        "application/pdf",
+        "application/zip",
+        "application/gzip",
+        "application/x-gzip",
+        "application/x-tar",
+        "application/x-protobuf",
+        "application/protobuf",
+        "application/grpc",
+        "application/wasm",
+        "application/msgpack",
+    ];
+    if BINARY_PREFIXES.iter().any(|p| mime.starts_with(p)) && mime != "image/svg+xml" {
+        return true;
+    }
+    if BINARY_TYPES.contains(&mime.as_str()) {
+        return true;
+    }
+    sniff_binary(&body[..body.len().min(SNIFF_LEN)])
+}
+
+fn sniff_binary(head: &[u8]) -> bool {
+    const MAGIC: [&[u8]; 8] = [
+        b"\x89PNG",
+        b"GIF8",
+        b"\xFF\xD8\xFF",
+        b"%PDF",
+        b"PK\x03\x04",
+        b"\x1F\x8B",
+        b"\0asm",
+        b"BZh",
+    ];
+    if MAGIC.iter().any(|m| head.starts_with(m)) || head.contains(&0) {
+        return true;
+    }
+    let text = match std::str::from_utf8(head) {
+        Ok(text) => text,
+        // A multi-byte character cut off at the sniff boundary is still text.
+        Err(e) if e.error_len().is_none() => std::str::from_utf8(&head[..e.valid_up_to()]).unwrap_or_default(),
+        Err(_) => return true,
+    };
+    let total    = text.chars().count().max(1);
+    let controls = text.chars().filter(|c| c.is_control() && !c.is_whitespace()).count();
+    controls * 10 > total
 }
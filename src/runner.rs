@@ -0,0 +1,222 @@
+use crate::assertion::AssertionFailure;
+use crate::auth::{Auth, REDACTED};
+use crate::charset;
+use crate::cli::Cli;
+use crate::client::{self, HttpClient};
+use crate::collections::Collection;
+use crate::config::AppConfig;
+use crate::display;
+use crate::environment;
+use crate::error::{AppError, Result};
+use crate::report::{self, ReportSpec};
+use crate::request::HttpRequest;
+use crate::response::ParsedResponse;
+use crate::settings::Settings;
+use colored::Colorize;
+use std::time::Instant;
+
+/// Outcome of one request within a collection run.
+#[derive(Debug)]
+pub struct RequestOutcome {
+    pub key:        String,
+    pub method:     String,
+    pub url:        String,
+    pub status:     Option<u16>,
+    pub elapsed_ms: u128,
+    pub error:      Option<String>,
+    pub failures:   Vec<AssertionFailure>,
+    /// The request as sent, after variable expansion.
+    pub request:    Option<HttpRequest>,
+    pub response:   Option<ParsedResponse>,
+}
+
+impl RequestOutcome {
+    pub fn passed(&self) -> bool {
+        self.error.is_none() && self.status.is_some() && self.failures.is_empty()
+    }
+}
+
+// ── public API ────────────────────────────────────────────────────────────────
+
+/// Run every request in `col` sequentially, printing a pass/fail line for each
+/// and a summary table at the end, then write any requested reports. Fails if
+/// any request failed.
+pub async fn run_collection(
+    col: &Collection,
+    reports: &[ReportSpec],
+    cli: &Cli,
+    config: &AppConfig,
+) -> Result<()> {
+    let http = HttpClient::from_settings(&Settings::resolve(cli, config))?.with_encoding(charset::from_cli(cli)?);
+    let mut vars = environment::load_selected(cli.env.as_deref())?;
+    let cli_auth = Auth::from_cli(cli)?;
+
+    if !cli.quiet {
+        display::print_header(&format!("Running collection: {}", col.name));
+    }
+
+    let mut prompted = Vec::new();
+    let mut outcomes = Vec::new();
+    for (key, saved) in col.ordered_requests() {
+        let mut outcome = RequestOutcome {
+            key:        key.to_string(),
+            method:     saved.request.method.to_string(),
+            url:        saved.request.url.clone(),
+            status:     None,
+            elapsed_ms: 0,
+            error:      None,
+            failures:   Vec::new(),
+            request:    None,
+            response:   None,
+        };
+
+        let start  = Instant::now();
+        let result = match environment::expand_request(&saved.request, &vars, config.base_url.as_deref()) {
+            Ok(mut req) => {
+                if let Some(a) = &cli_auth {
+                    req.auth = Some(a.clone());
+                }
+                let result = match req.auth.take().map(|a| resolve_auth(a, &mut prompted)).transpose() {
+                    Ok(auth) => {
+                        req.auth = auth;
+                        client::exchange(&http, &req, config).await
+                    }
+                    Err(e) => Err(e),
+                };
+                outcome.request = Some(req);
+                result
+            }
+            Err(e) => Err(e),
+        };
+
+        let mut captured = Vec::new();
+        match result {
+            Ok(parsed) => {
+                outcome.status     = Some(parsed.status);
+                outcome.elapsed_ms = parsed.elapsed_ms;
+                outcome.failures   = saved.assertions.evaluate(&parsed);
+                match saved.capture.apply(&parsed, &mut vars) {
+                    Ok(values) => captured = values,
+                    Err(e)     => outcome.error = Some(e.to_string()),
+                }
+                outcome.response = Some(parsed);
+            }
+            Err(e) => {
+                outcome.error      = Some(e.to_string());
+                outcome.elapsed_ms = start.elapsed().as_millis();
+            }
+        }
+
+        if !cli.quiet {
+            print_outcome_line(&outcome);
+            for f in &outcome.failures {
+                println!("      {} {}", "✗".red(), f.to_string().red());
+            }
+            if cli.verbose {
+                for (name, value) in &captured {
+                    let masked = !cli.show_secrets && saved.capture.is_sensitive(name, &config.redact);
+                    let value  = if masked { REDACTED } else { value.as_str() };
+                    println!("      {} {} = {value}", "↳".dimmed(), name.cyan());
+                }
+            }
+        }
+        outcomes.push(outcome);
+    }
+
+    if !cli.quiet {
+        print_summary(&outcomes);
+    }
+
+    for spec in reports {
+        report::write(spec, &col.name, &outcomes, config)?;
+        if !cli.quiet {
+            display::print_info(&format!("Wrote {} report to {}", spec.format, spec.path.display()));
+        }
+    }
+
+    let errored = outcomes.iter().filter(|o| o.error.is_some() || o.status.is_none()).count();
+    if errored > 0 {
+        return Err(AppError::Other(format!(
+            "{errored} of {} requests failed",
+            outcomes.len()
+        )));
+    }
+    let failed: usize = outcomes.iter().map(|o| o.failures.len()).sum();
+    if failed > 0 {
+        return Err(AppError::AssertionsFailed { failed });
+    }
+    Ok(())
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+/// Prompt for a missing password at most once per distinct credential, so
+/// requests sharing an auth block reuse the first answer.
+fn resolve_auth(auth: Auth, prompted: &mut Vec<(Auth, Auth)>) -> Result<Auth> {
+    if let Some((_, resolved)) = prompted.iter().find(|(asked, _)| *asked == auth) {
+        return Ok(resolved.clone());
+    }
+    let resolved = auth.clone().with_prompted_password()?;
+    if resolved != auth {
+        prompted.push((auth, resolved.clone()));
+    }
+    Ok(resolved)
+}
+
+fn print_outcome_line(o: &RequestOutcome) {
+    let mark = if o.passed() { "✓".green().bold() } else { "✗".red().bold() };
+    let detail = match (&o.error, o.status) {
+        (Some(err), Some(s)) => format!("{}  {}", status_label(s), err.red()),
+        (Some(err), None)    => err.red().to_string(),
+        (None, Some(s))      => status_label(s),
+        (None, None)         => String::new(),
+    };
+    println!(
+        "  {mark} {:24} {:6} {}  {}",
+        o.key,
+        o.method,
+        detail,
+        format!("({}ms)", o.elapsed_ms).dimmed()
+    );
+}
+
+fn print_summary(outcomes: &[RequestOutcome]) {
+    let passed = outcomes.iter().filter(|o| o.passed()).count();
+    let failed = outcomes.len() - passed;
+    let total_ms: u128 = outcomes.iter().map(|o| o.elapsed_ms).sum();
+
+    display::print_header("Summary");
+    println!("  {:24} {:6} {:>6} {:>8}  RESULT", "REQUEST", "METHOD", "STATUS", "TIME");
+    for o in outcomes {
+        let status = o.status.map_or_else(|| "-".to_string(), |s| s.to_string());
+        let result = if o.passed() { "pass".green() } else { "FAIL".red().bold() };
+        println!(
+            "  {:24} {:6} {:>6} {:>6}ms  {}",
+            o.key, o.method, status, o.elapsed_ms, result
+        );
+    }
+    println!();
+    let totals = format!(
+        "{} passed, {} failed, {} total in {}ms",
+        passed,
+        failed,
+        outcomes.len(),
+        total_ms
+    );
+    if failed == 0 {
+        println!("  {}", totals.green());
+    } else {
+        println!("  {}", totals.red());
+    }
+}
+
+fn status_label(code: u16) -> String {
+    let s = code.to_string();
+    match code {
+        200..=299 => s.green().to_string(),
+        300..=399 => s.yellow().to_string(),
+        400..=499 => s.red().to_string(),
+        500..=599 => s.bright_red().to_string(),
+        _         => s,
+    }
+}
@@ -0,0 +1,46 @@
+use crate::cli::Cli;
+use crate::config::AppConfig;
+
+/// Effective HTTP client settings after layering, lowest precedence first:
+/// built-in defaults → config file → `RR_*` environment variables → CLI flags.
+///
+/// Environment variables are read by clap alongside their flags, so a CLI
+/// value that is unset here means neither the flag nor its variable was given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    pub timeout_secs:     u64,
+    pub follow_redirects: bool,
+    pub max_redirects:    usize,
+    pub insecure:         bool,
+}
+
+impl Settings {
+    /// Settings taken from the config file alone (used by the TUI).
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            timeout_secs:     config.timeout_secs,
+            follow_redirects: config.follow_redirects,
+            max_redirects:    config.max_redirects,
+            insecure:         false,
+        }
+    }
+
+    /// Merge CLI flags (and their environment variables) over the config file.
+    pub fn resolve(cli: &Cli, config: &AppConfig) -> Self {
+        let mut settings = Self::from_config(config);
+
+        if let Some(secs) = cli.timeout {
+            settings.timeout_secs = secs;
+        }
+        if let Some(max) = cli.max_redirects {
+            settings.max_redirects    = max;
+            settings.follow_redirects = true;
+        }
+        if cli.no_follow {
+            settings.follow_redirects = false;
+        }
+        settings.insecure = cli.insecure;
+
+        settings
+    }
+}
@@ -0,0 +1,217 @@
+use crate::error::{AppError, Result};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// Settings for the `aws-sigv4` auth type. Omitted credentials and region
+/// are read from the standard `AWS_*` environment variables at send time.
+///
+/// ```json
+/// "auth": { "type": "aws-sigv4", "service": "execute-api", "region": "eu-west-1" }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AwsSigV4Config {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_key_id:     Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_access_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_token:     Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region:            Option<String>,
+    pub service:           String,
+}
+
+/// Credentials and scope after environment fallbacks have been applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub access_key_id:     String,
+    pub secret_access_key: String,
+    pub session_token:     Option<String>,
+    pub region:            String,
+    pub service:           String,
+}
+
+impl AwsSigV4Config {
+    /// Parse the `REGION:SERVICE` value of `--aws-sigv4`.
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let (region, service) = spec
+            .split_once(':')
+            .filter(|(r, s)| !r.is_empty() && !s.is_empty())
+            .ok_or_else(|| AppError::Signing(format!("Invalid --aws-sigv4 '{spec}' — expected REGION:SERVICE")))?;
+        Ok(Self {
+            access_key_id:     None,
+            secret_access_key: None,
+            session_token:     None,
+            region:            Some(region.to_string()),
+            service:           service.to_string(),
+        })
+    }
+
+    /// Fill missing values from `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY`,
+    /// `AWS_SESSION_TOKEN` and `AWS_REGION` / `AWS_DEFAULT_REGION`.
+    pub fn resolve(&self) -> Result<Credentials> {
+        let env = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let required = |value: Option<String>, field: &str, var: &str| {
+            value.ok_or_else(|| AppError::Signing(format!("AWS {field} missing — set '{field}' or {var}")))
+        };
+
+        Ok(Credentials {
+            access_key_id:     required(self.access_key_id.clone().or_else(|| env("AWS_ACCESS_KEY_ID")), "access_key_id", "AWS_ACCESS_KEY_ID")?,
+            secret_access_key: required(self.secret_access_key.clone().or_else(|| env("AWS_SECRET_ACCESS_KEY")), "secret_access_key", "AWS_SECRET_ACCESS_KEY")?,
+            session_token:     self.session_token.clone().or_else(|| env("AWS_SESSION_TOKEN")),
+            region:            required(
+                self.region.clone().or_else(|| env("AWS_REGION")).or_else(|| env("AWS_DEFAULT_REGION")),
+                "region",
+                "AWS_REGION",
+            )?,
+            service:           self.service.clone(),
+        })
+    }
+}
+
+/// Add `x-amz-date`, the optional session token and the `Authorization`
+/// header to a fully built request.
+pub fn sign(request: &mut reqwest::Request, creds: &Credentials, now: DateTime<Utc>) -> Result<()> {
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date     = now.format("%Y%m%d").to_string();
+    let payload  = request
+        .body()
+        .map(|b| b.as_bytes().map(sha256_hex).unwrap_or_else(|| "UNSIGNED-PAYLOAD".to_string()))
+        .unwrap_or_else(|| sha256_hex(b""));
+
+    insert(request, "x-amz-date", &amz_date)?;
+    if let Some(token) = &creds.session_token {
+        insert(request, "x-amz-security-token", token)?;
+    }
+    // S3 requires the payload hash as a header; other services only sign it.
+    if creds.service == "s3" {
+        insert(request, "x-amz-content-sha256", &payload)?;
+    }
+
+    let (canonical, signed_headers) = canonical_request(request, &creds.service, &payload)?;
+    let scope = format!("{date}/{}/{}/aws4_request", creds.region, creds.service);
+    let string_to_sign = format!("{ALGORITHM}\n{amz_date}\n{scope}\n{}", sha256_hex(canonical.as_bytes()));
+
+    let mut key = hmac(format!("AWS4{}", creds.secret_access_key).as_bytes(), date.as_bytes());
+    for part in [creds.region.as_str(), creds.service.as_str(), "aws4_request"] {
+        key = hmac(&key, part.as_bytes());
+    }
+    let signature = hex(&hmac(&key, string_to_sign.as_bytes()));
+
+    let authorization = format!(
+        "{ALGORITHM} Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+        creds.access_key_id
+    );
+    insert(request, "authorization", &authorization)
+}
+
+/// Build the canonical request string and its signed-header list.
+pub fn canonical_request(request: &reqwest::Request, service: &str, payload_hash: &str) -> Result<(String, String)> {
+    let url = request.url();
+
+    // S3 paths are encoded once; every other service double-encodes.
+    let path = if url.path().is_empty() { "/" } else { url.path() };
+    let canonical_uri = path
+        .split('/')
+        .map(|seg| {
+            let once = uri_encode(&percent_decode(seg));
+            if service == "s3" { once } else { uri_encode(&once) }
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let mut query: Vec<(String, String)> = url
+        .query_pairs()
+        .map(|(k, v)| (uri_encode(&k), uri_encode(&v)))
+        .collect();
+    query.sort();
+    let canonical_query = query
+        .iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    let mut headers: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let host = match url.port() {
+        Some(port) => format!("{}:{port}", url.host_str().unwrap_or_default()),
+        None       => url.host_str().unwrap_or_default().to_string(),
+    };
+    headers.insert("host".to_string(), vec![host]);
+    for (name, value) in request.headers() {
+        let value = value
+            .to_str()
+            .map_err(|_| AppError::Signing(format!("Header '{name}' is not valid text")))?;
+        let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+        headers.entry(name.as_str().to_string()).or_default().push(value);
+    }
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, values)| format!("{name}:{}\n", values.join(",")))
+        .collect();
+    let signed_headers = headers.keys().cloned().collect::<Vec<_>>().join(";");
+
+    let canonical = format!(
+        "{}\n{canonical_uri}\n{canonical_query}\n{canonical_headers}\n{signed_headers}\n{payload_hash}",
+        request.method().as_str()
+    );
+    Ok((canonical, signed_headers))
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+fn insert(request: &mut reqwest::Request, name: &'static str, value: &str) -> Result<()> {
+    let value = reqwest::header::HeaderValue::from_str(value)
+        .map_err(|_| AppError::Signing(format!("Invalid value for '{name}'")))?;
+    request.headers_mut().insert(name, value);
+    Ok(())
+}
+
+/// Percent-encode everything except RFC 3986 unreserved characters.
+fn uri_encode(input: &str) -> String {
+    input
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok());
+        match (bytes[i], hex.and_then(|h| u8::from_str_radix(h, 16).ok())) {
+            (b'%', Some(decoded)) => {
+                out.push(decoded);
+                i += 3;
+            }
+            (b, _) => {
+                out.push(b);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn hmac(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    hex(&Sha256::digest(data))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -35,6 +35,8 @@ pub struct App {
 
     // ── last response ────────────────────────────────────────────────────────
     pub response_text: String,
+    /// `response_text` is pretty-printed JSON and can be highlighted.
+    pub response_json: bool,
     pub status_code:   Option<u16>,
     pub elapsed_ms:    Option<u128>,
 
@@ -44,8 +46,19 @@ pub struct App {
     pub config: AppConfig,
 }
 
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl App {
     pub fn new() -> Self {
+        // Fall back to defaults so the TUI still opens, but say why.
+        let (config, error_message) = match AppConfig::load().and_then(|c| c.with_profile(None)) {
+            Ok(config) => (config, None),
+            Err(e)     => (AppConfig::default(), Some(format!("Config error (using defaults): {e}"))),
+        };
         Self {
             mode:          AppMode::Normal,
             focus:         FocusArea::UrlInput,
@@ -54,11 +67,12 @@ impl App {
             headers_raw:   String::new(),
             body_raw:      String::new(),
             response_text: String::new(),
+            response_json: false,
             status_code:   None,
             elapsed_ms:    None,
             is_loading:    false,
-            error_message: None,
-            config:        AppConfig::load().unwrap_or_default(),
+            error_message,
+            config,
         }
     }
 
@@ -96,6 +110,7 @@ impl App {
             headers: HashMap::new(),
             query:   HashMap::new(),
             body:    None,
+            auth:    None,
         };
 
         let client = HttpClient::new(&self.config)?;
@@ -106,10 +121,11 @@ impl App {
                 self.status_code = Some(resp.status().as_u16());
                 self.elapsed_ms  = Some(start.elapsed().as_millis());
                 let body = resp.text().await.unwrap_or_default();
-                self.response_text = serde_json::from_str::<serde_json::Value>(&body)
+                let pretty = serde_json::from_str::<serde_json::Value>(&body)
                     .ok()
-                    .and_then(|v| serde_json::to_string_pretty(&v).ok())
-                    .unwrap_or(body);
+                    .and_then(|v| serde_json::to_string_pretty(&v).ok());
+                self.response_json = pretty.is_some();
+                self.response_text = pretty.unwrap_or(body);
             }
             Err(e) => {
                 self.error_message = Some(e.to_string());
@@ -1,8 +1,9 @@
 use super::app::{App, FocusArea};
+use crate::highlight::{self, TokenKind};
 use ratatui::{
     layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
+    text::{Line, Span, Text},
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
@@ -96,13 +97,44 @@ fn render_body_response(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
         (_, Some(s)) => format!("Response  [{s}]"),
         _            => "Response".to_string(),
     };
-    let response = Paragraph::new(app.response_text.as_str())
+    let text = if app.response_json && highlight::enabled() {
+        highlighted_json(&app.response_text)
+    } else {
+        Text::raw(app.response_text.as_str())
+    };
+    let response = Paragraph::new(text)
         .block(Block::default().title(resp_title).borders(Borders::ALL))
         .style(focused(app.focus == FocusArea::ResponseView))
         .wrap(Wrap { trim: false });
     f.render_widget(response, chunks[1]);
 }
 
+/// Style JSON with the same token palette as the CLI.
+fn highlighted_json(json: &str) -> Text<'_> {
+    let mut lines = vec![Line::default()];
+    for token in highlight::tokens(json) {
+        let style = match token.kind {
+            TokenKind::Key     => Style::default().fg(Color::Cyan),
+            TokenKind::String  => Style::default().fg(Color::Yellow),
+            TokenKind::Number  => Style::default().fg(Color::Magenta),
+            TokenKind::Boolean => Style::default().fg(Color::Blue),
+            TokenKind::Null    => Style::default().add_modifier(Modifier::DIM),
+            TokenKind::Punctuation | TokenKind::Whitespace => Style::default(),
+        };
+        for (i, part) in token.text.split('\n').enumerate() {
+            if i > 0 {
+                lines.push(Line::default());
+            }
+            if !part.is_empty() {
+                if let Some(line) = lines.last_mut() {
+                    line.spans.push(Span::styled(part, style));
+                }
+            }
+        }
+    }
+    Text::from(lines)
+}
+
 fn render_status_bar(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
     let span = if let Some(err) = &app.error_message {
         Span::styled(err.as_str(), Style::default().fg(Color::Red))
@@ -0,0 +1,221 @@
+use crate::cli::SecretAction;
+use crate::config::{self, AppConfig};
+use crate::display;
+use crate::environment;
+use crate::error::{AppError, Result};
+use argon2::Argon2;
+use base64::engine::general_purpose::STANDARD as B64;
+use base64::Engine as _;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Environment variable read before prompting for the vault passphrase.
+pub const PASSPHRASE_ENV: &str = "RR_VAULT_PASSPHRASE";
+
+/// Prefix of `{{secret:name}}` references.
+pub const SECRET_PREFIX: &str = "secret:";
+
+/// On-disk format: the secret map as JSON, encrypted with ChaCha20-Poly1305
+/// under a key derived from the passphrase with Argon2id.
+#[derive(Serialize, Deserialize)]
+struct VaultFile {
+    version:    u32,
+    kdf:        String,
+    salt:       String,
+    nonce:      String,
+    ciphertext: String,
+}
+
+/// Named secrets stored encrypted in `~/.config/rustrest/secrets.vault`.
+pub struct Vault {
+    path:       PathBuf,
+    passphrase: String,
+    secrets:    BTreeMap<String, String>,
+}
+
+impl Vault {
+    pub fn default_path() -> PathBuf {
+        AppConfig::config_dir().join("secrets.vault")
+    }
+
+    /// Open the vault at the default path, reading the passphrase from
+    /// `RR_VAULT_PASSPHRASE` or prompting for it.
+    pub fn unlock() -> Result<Self> {
+        let path = Self::default_path();
+        let passphrase = match std::env::var(PASSPHRASE_ENV) {
+            Ok(p) if !p.is_empty() => p,
+            _ => prompt_passphrase(!path.exists())?,
+        };
+        Self::open(&path, &passphrase)
+    }
+
+    /// Decrypt the vault at `path`; a missing file is an empty vault.
+    pub fn open(path: &Path, passphrase: &str) -> Result<Self> {
+        let mut vault = Self {
+            path:       path.to_path_buf(),
+            passphrase: passphrase.to_string(),
+            secrets:    BTreeMap::new(),
+        };
+        if !path.exists() {
+            return Ok(vault);
+        }
+
+        let file: VaultFile = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        if file.version != 1 || file.kdf != "argon2id" {
+            return Err(AppError::Vault(format!("Unsupported vault format in {}", path.display())));
+        }
+        let salt       = decode(&file.salt)?;
+        let nonce      = decode(&file.nonce)?;
+        let ciphertext = decode(&file.ciphertext)?;
+        if nonce.len() != 12 {
+            return Err(AppError::Vault("Corrupt vault nonce".into()));
+        }
+
+        let cipher = ChaCha20Poly1305::new(&derive_key(passphrase, &salt)?);
+        let plain  = cipher
+            .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| AppError::Vault("Wrong passphrase or corrupt vault".into()))?;
+        vault.secrets = serde_json::from_slice(&plain)?;
+        Ok(vault)
+    }
+
+    /// Re-encrypt and write the vault with a fresh salt and nonce.
+    pub fn save(&self) -> Result<()> {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let nonce  = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let cipher = ChaCha20Poly1305::new(&derive_key(&self.passphrase, &salt)?);
+        let ciphertext = cipher
+            .encrypt(&nonce, serde_json::to_vec(&self.secrets)?.as_slice())
+            .map_err(|_| AppError::Vault("Encryption failed".into()))?;
+
+        let file = VaultFile {
+            version:    1,
+            kdf:        "argon2id".to_string(),
+            salt:       B64.encode(salt),
+            nonce:      B64.encode(nonce),
+            ciphertext: B64.encode(ciphertext),
+        };
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        config::write_private(&self.path, serde_json::to_string_pretty(&file)?.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.secrets.get(name).map(String::as_str)
+    }
+
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.secrets.insert(name.to_string(), value.to_string());
+    }
+
+    /// Remove a secret, returning whether it existed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.secrets.remove(name).is_some()
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.secrets.keys().map(String::as_str)
+    }
+
+    /// Replace every `{{secret:name}}` reference in `input`.
+    pub fn expand(&self, input: &str) -> Result<String> {
+        let mut out  = String::with_capacity(input.len());
+        let mut rest = input;
+
+        while let Some((span, inner)) = environment::next_placeholder(rest) {
+            match inner.strip_prefix(SECRET_PREFIX) {
+                Some(name) => {
+                    out.push_str(&rest[..span.start]);
+                    out.push_str(self.get(name.trim()).ok_or_else(|| not_found(name.trim()))?);
+                }
+                None => out.push_str(&rest[..span.end]),
+            }
+            rest = &rest[span.end..];
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+}
+
+/// Whether `input` contains a `{{secret:name}}` reference, spaced or not.
+pub fn references_secrets(input: &str) -> bool {
+    let mut rest = input;
+    while let Some((span, name)) = environment::next_placeholder(rest) {
+        if name.starts_with(SECRET_PREFIX) {
+            return true;
+        }
+        rest = &rest[span.end..];
+    }
+    false
+}
+
+// ── CLI ───────────────────────────────────────────────────────────────────────
+
+pub fn handle_action(action: &SecretAction) -> Result<()> {
+    let mut vault = Vault::unlock()?;
+    match action {
+        SecretAction::Set { name, value } => {
+            let value = match value {
+                Some(v) => v.clone(),
+                None    => rpassword::prompt_password(format!("Value for '{name}': "))?,
+            };
+            vault.set(name, &value);
+            vault.save()?;
+            display::print_success(&format!("Stored secret '{name}'"));
+        }
+        SecretAction::Get { name } => {
+            println!("{}", vault.get(name).ok_or_else(|| not_found(name))?);
+        }
+        SecretAction::List => {
+            let mut empty = true;
+            for name in vault.names() {
+                println!("{name}");
+                empty = false;
+            }
+            if empty {
+                display::print_info("No secrets stored");
+            }
+        }
+        SecretAction::Rm { name } => {
+            if !vault.remove(name) {
+                return Err(not_found(name));
+            }
+            vault.save()?;
+            display::print_success(&format!("Removed secret '{name}'"));
+        }
+    }
+    Ok(())
+}
+
+// ── private helpers ───────────────────────────────────────────────────────────
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<Key> {
+    let mut key = Key::default();
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| AppError::Vault(format!("Key derivation failed: {e}")))?;
+    Ok(key)
+}
+
+fn prompt_passphrase(create: bool) -> Result<String> {
+    let passphrase = rpassword::prompt_password("Vault passphrase: ")?;
+    if create && rpassword::prompt_password("Confirm new vault passphrase: ")? != passphrase {
+        return Err(AppError::Vault("Passphrases do not match".into()));
+    }
+    Ok(passphrase)
+}
+
+fn decode(value: &str) -> Result<Vec<u8>> {
+    B64.decode(value).map_err(|_| AppError::Vault("Corrupt vault file".into()))
+}
+
+fn not_found(name: &str) -> AppError {
+    AppError::Vault(format!("Secret '{name}' not found — add it with 'rr secret set {name}'"))
+}
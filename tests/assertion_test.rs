@@ -0,0 +1,77 @@
+use rustrest::assertion::Assertions;
+use rustrest::response::ParsedResponse;
+use serde_json::json;
+
+fn response(status: u16, body: &str) -> ParsedResponse {
+    ParsedResponse {
+        status,
+        status_text:  "OK".to_string(),
+        headers:      vec![("content-type".to_string(), "application/json; charset=utf-8".to_string())],
+        body:         body.as_bytes().to_vec(),
+        content_type: "application/json".to_string(),
+        size_bytes:   body.len(),
+        elapsed_ms:   120,
+        charset:      Default::default(),
+    }
+}
+
+fn assertions(v: serde_json::Value) -> Assertions {
+    serde_json::from_value(v).expect("valid assertions")
+}
+
+#[test]
+fn empty_assertions_only_require_non_error_status() {
+    let a = Assertions::default();
+    assert!(a.evaluate(&response(204, "")).is_empty());
+    let failures = a.evaluate(&response(500, ""));
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].check, "status");
+}
+
+#[test]
+fn status_equals_and_range() {
+    assert!(assertions(json!({ "status": 404 })).evaluate(&response(404, "")).is_empty());
+    assert!(assertions(json!({ "status": { "min": 200, "max": 299 } })).evaluate(&response(201, "")).is_empty());
+
+    let failures = assertions(json!({ "status": { "min": 200, "max": 299 } })).evaluate(&response(302, ""));
+    assert_eq!(failures[0].expected, "200..=299");
+    assert_eq!(failures[0].actual, "302");
+}
+
+#[test]
+fn header_checks() {
+    let a = assertions(json!({
+        "headers": {
+            "Content-Type": { "matches": "^application/json" },
+            "X-Missing":    { "present": false },
+            "X-Required":   { "present": true }
+        }
+    }));
+    let failures = a.evaluate(&response(200, "{}"));
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].check, "header X-Required");
+}
+
+#[test]
+fn body_checks_report_expected_and_actual() {
+    let a = assertions(json!({
+        "body": [
+            { "path": "$.id",    "equals": 7 },
+            { "path": "$.name",  "contains": "lic" },
+            { "path": "$.tags",  "contains": "b" },
+            { "path": "$.tags",  "type": "object" }
+        ]
+    }));
+    let failures = a.evaluate(&response(200, r#"{"id":8,"name":"Alice","tags":["a","b"]}"#));
+    assert_eq!(failures.len(), 2);
+    assert_eq!((failures[0].expected.as_str(), failures[0].actual.as_str()), ("7", "8"));
+    assert_eq!(failures[1].actual, "type array");
+}
+
+#[test]
+fn time_and_size_limits() {
+    let a = assertions(json!({ "max_time_ms": 100, "max_size_bytes": 4 }));
+    let failures = a.evaluate(&response(200, "{\"a\":1}"));
+    let checks: Vec<_> = failures.iter().map(|f| f.check.as_str()).collect();
+    assert_eq!(checks, ["response time", "body size"]);
+}
@@ -0,0 +1,88 @@
+use clap::Parser;
+use rustrest::auth::{Auth, REDACTED};
+use rustrest::cli::Cli;
+use rustrest::client::HttpClient;
+use rustrest::config::AppConfig;
+use rustrest::request::{HttpMethod, HttpRequest};
+use std::collections::HashMap;
+
+fn request(url: String, auth: Option<Auth>) -> HttpRequest {
+    HttpRequest {
+        method:  HttpMethod::Get,
+        url,
+        headers: HashMap::new(),
+        query:   HashMap::new(),
+        body:    None,
+        auth,
+    }
+}
+
+#[test]
+fn auth_deserializes_from_collection_json() {
+    let auth: Auth = serde_json::from_str(r#"{ "type": "bearer", "token": "{{token}}" }"#).unwrap();
+    assert_eq!(auth, Auth::Bearer { token: "{{token}}".to_string() });
+
+    let auth: Auth = serde_json::from_str(r#"{ "type": "basic", "username": "alice" }"#).unwrap();
+    assert_eq!(auth, Auth::Basic { username: "alice".to_string(), password: None });
+}
+
+#[test]
+fn cli_flags_build_auth() {
+    let cli = Cli::try_parse_from(["rr", "--auth", "alice:s3cret", "get", "http://x"]).unwrap();
+    assert_eq!(
+        Auth::from_cli(&cli).unwrap(),
+        Some(Auth::Basic { username: "alice".to_string(), password: Some("s3cret".to_string()) })
+    );
+
+    let cli = Cli::try_parse_from(["rr", "--bearer", "tok", "get", "http://x"]).unwrap();
+    assert_eq!(Auth::from_cli(&cli).unwrap(), Some(Auth::Bearer { token: "tok".to_string() }));
+
+    let cli = Cli::try_parse_from(["rr", "--auth", "admin:pw", "--digest", "get", "http://x"]).unwrap();
+    assert_eq!(
+        Auth::from_cli(&cli).unwrap(),
+        Some(Auth::Digest { username: "admin".to_string(), password: Some("pw".to_string()) })
+    );
+
+    assert!(Cli::try_parse_from(["rr", "--auth", "a:b", "--bearer", "t", "get", "http://x"]).is_err());
+    assert!(Cli::try_parse_from(["rr", "--digest", "get", "http://x"]).is_err());
+}
+
+#[test]
+fn redacted_auth_hides_secrets() {
+    let basic = Auth::Basic { username: "alice".to_string(), password: Some("s3cret".to_string()) };
+    assert_eq!(
+        basic.redacted(),
+        Auth::Basic { username: "alice".to_string(), password: Some(REDACTED.to_string()) }
+    );
+    let json = serde_json::to_string(&Auth::Bearer { token: "tok".to_string() }.redacted()).unwrap();
+    assert!(!json.contains("tok\""));
+}
+
+#[tokio::test]
+async fn send_applies_basic_and_bearer_credentials() {
+    let mut server = mockito::Server::new_async().await;
+    let basic = server
+        .mock("GET", "/basic")
+        .match_header("authorization", "Basic YWxpY2U6czNjcmV0")
+        .with_status(200)
+        .create_async()
+        .await;
+    let bearer = server
+        .mock("GET", "/bearer")
+        .match_header("authorization", "Bearer tok")
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let http = HttpClient::new(&AppConfig::default()).unwrap();
+    let creds = Auth::Basic { username: "alice".to_string(), password: Some("s3cret".to_string()) };
+    let resp = http.send(request(format!("{}/basic", server.url()), Some(creds))).await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let token = Auth::Bearer { token: "tok".to_string() };
+    let resp = http.send(request(format!("{}/bearer", server.url()), Some(token))).await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    basic.assert_async().await;
+    bearer.assert_async().await;
+}
@@ -0,0 +1,66 @@
+use clap::Parser;
+use rustrest::cli::Cli;
+use rustrest::client::HttpClient;
+use rustrest::config::AppConfig;
+use rustrest::request::{HttpMethod, HttpRequest, RequestBody};
+use std::collections::HashMap;
+
+fn file_request(url: String, path: &str, headers: HashMap<String, String>) -> HttpRequest {
+    HttpRequest {
+        method: HttpMethod::Put,
+        url,
+        headers,
+        query: HashMap::new(),
+        body: Some(RequestBody::File(path.to_string())),
+        auth: None,
+    }
+}
+
+#[test]
+fn at_dash_reads_stdin() {
+    let cli = Cli::try_parse_from(["rr", "post", "http://x", "--body", "@-"]).unwrap();
+    let req = HttpRequest::from_command(&cli.command, &AppConfig::default()).unwrap();
+    assert!(matches!(req.body, Some(RequestBody::File(ref p)) if p == "-"));
+}
+
+#[tokio::test]
+async fn file_bodies_stream_with_length_and_inferred_type() {
+    let path = std::env::temp_dir().join(format!("rustrest-stream-{}.json", std::process::id()));
+    let data = format!("{{\"items\":[{}]}}", vec!["1"; 50_000].join(","));
+    std::fs::write(&path, &data).unwrap();
+
+    let mut server = mockito::Server::new_async().await;
+    let inferred = server
+        .mock("PUT", "/inferred")
+        .match_header("content-type", "application/json")
+        .match_header("content-length", data.len().to_string().as_str())
+        .match_body(data.as_str())
+        .create_async()
+        .await;
+    let explicit = server
+        .mock("PUT", "/explicit")
+        .match_header("content-type", "text/plain")
+        .create_async()
+        .await;
+
+    let http = HttpClient::new(&AppConfig::default()).unwrap().with_progress(true);
+    let p    = path.to_str().unwrap();
+    http.send(file_request(format!("{}/inferred", server.url()), p, HashMap::new())).await.unwrap();
+
+    let headers = [("Content-Type".to_string(), "text/plain".to_string())].into();
+    http.send(file_request(format!("{}/explicit", server.url()), p, headers)).await.unwrap();
+
+    inferred.assert_async().await;
+    explicit.assert_async().await;
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn missing_file_names_the_path() {
+    let http = HttpClient::new(&AppConfig::default()).unwrap();
+    let err  = http
+        .send(file_request("http://127.0.0.1:9/".to_string(), "/nonexistent/body.bin", HashMap::new()))
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("/nonexistent/body.bin"), "{err}");
+}
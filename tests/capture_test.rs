@@ -0,0 +1,78 @@
+use rustrest::capture::Capture;
+use rustrest::environment::Variables;
+use rustrest::error::AppError;
+use rustrest::jsonpath::select;
+use rustrest::redact::Redaction;
+use rustrest::response::ParsedResponse;
+use serde_json::json;
+
+fn response(body: &str) -> ParsedResponse {
+    ParsedResponse {
+        status:       200,
+        status_text:  "OK".to_string(),
+        headers:      vec![("x-request-id".to_string(), "req-7".to_string())],
+        body:         body.as_bytes().to_vec(),
+        content_type: "application/json".to_string(),
+        size_bytes:   body.len(),
+        elapsed_ms:   5,
+        charset:      Default::default(),
+    }
+}
+
+#[test]
+fn select_supports_jsonpath_and_pointer() {
+    let v = json!({ "data": { "items": [{ "id": 1 }, { "id": 2 }], "odd key": true } });
+    assert_eq!(select(&v, "$.data.items[1].id").unwrap(), Some(&json!(2)));
+    assert_eq!(select(&v, "$['data']['odd key']").unwrap(), Some(&json!(true)));
+    assert_eq!(select(&v, "/data/items/0/id").unwrap(), Some(&json!(1)));
+    assert_eq!(select(&v, "$").unwrap(), Some(&v));
+    assert_eq!(select(&v, "$.data.missing").unwrap(), None);
+}
+
+#[test]
+fn select_rejects_malformed_paths() {
+    let v = json!({});
+    assert!(matches!(select(&v, "data.id"), Err(AppError::InvalidJsonPath { .. })));
+    assert!(matches!(select(&v, "$.items[x]"), Err(AppError::InvalidJsonPath { .. })));
+}
+
+#[test]
+fn capture_writes_body_and_header_values_into_scope() {
+    let capture: Capture = serde_json::from_value(json!({
+        "body":    { "token": "$.auth.token", "user_id": "/auth/user" },
+        "headers": { "request_id": "X-Request-Id" }
+    }))
+    .unwrap();
+
+    let mut vars = Variables::new();
+    capture
+        .apply(&response(r#"{"auth":{"token":"t0k","user":12}}"#), &mut vars)
+        .unwrap();
+
+    assert_eq!(vars["token"], "t0k");
+    assert_eq!(vars["user_id"], "12");
+    assert_eq!(vars["request_id"], "req-7");
+}
+
+#[test]
+fn capture_fails_when_path_does_not_match() {
+    let capture: Capture = serde_json::from_value(json!({ "body": { "token": "$.nope" } })).unwrap();
+    let err = capture.apply(&response("{}"), &mut Variables::new()).unwrap_err();
+    assert!(matches!(err, AppError::Capture { name, .. } if name == "token"));
+}
+
+#[test]
+fn sensitive_captures_follow_redaction_rules() {
+    let capture: Capture = serde_json::from_value(json!({
+        "body":    { "token": "$.data.access_token", "session": "/session", "user": "$.user.id" },
+        "headers": { "cookie": "Set-Cookie", "request_id": "X-Request-Id" }
+    }))
+    .unwrap();
+    let redact = Redaction { body_paths: vec!["access_token".into(), "/session".into()], ..Redaction::default() };
+
+    assert!(capture.is_sensitive("token", &redact));
+    assert!(capture.is_sensitive("session", &redact));
+    assert!(capture.is_sensitive("cookie", &redact));
+    assert!(!capture.is_sensitive("user", &redact));
+    assert!(!capture.is_sensitive("request_id", &redact));
+}
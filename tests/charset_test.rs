@@ -0,0 +1,62 @@
+use clap::Parser;
+use encoding_rs::{SHIFT_JIS, UTF_16LE, UTF_8, WINDOWS_1252};
+use rustrest::charset::{self, Charset, CharsetSource};
+use rustrest::cli::Cli;
+
+#[test]
+fn content_type_charset_is_used() {
+    let cs = Charset::detect("text/plain; charset=\"ISO-8859-1\"", b"caf\xe9", None);
+    assert_eq!(cs, Charset { encoding: WINDOWS_1252, source: CharsetSource::ContentType });
+    assert_eq!(cs.decode(b"caf\xe9"), "café");
+
+    let cs = Charset::detect("text/plain;charset=Shift_JIS", b"", None);
+    assert_eq!(cs.encoding, SHIFT_JIS);
+    assert_eq!(cs.decode(b"\x82\xb1\x82\xf1"), "こん");
+}
+
+#[test]
+fn bom_wins_over_content_type_and_is_stripped() {
+    let body = b"\xff\xfeh\x00i\x00";
+    let cs = Charset::detect("text/plain; charset=utf-8", body, None);
+    assert_eq!(cs, Charset { encoding: UTF_16LE, source: CharsetSource::Bom });
+    assert_eq!(cs.decode(body), "hi");
+
+    let body = b"\xef\xbb\xbf{\"a\":1}";
+    assert_eq!(Charset::detect("application/json", body, None).decode(body), "{\"a\":1}");
+}
+
+#[test]
+fn html_meta_and_xml_declaration_are_read() {
+    let html = br#"<!doctype html><html><head><META http-equiv="Content-Type" content="text/html; charset=windows-1252"></head>"#;
+    let cs = Charset::detect("text/html", html, None);
+    assert_eq!(cs, Charset { encoding: WINDOWS_1252, source: CharsetSource::Document });
+
+    let html = b"<html><head><meta charset=shift_jis></head>";
+    assert_eq!(Charset::detect("text/html", html, None).encoding, SHIFT_JIS);
+
+    let xml = b"<?xml version=\"1.0\" encoding='ISO-8859-1'?><a/>";
+    assert_eq!(Charset::detect("application/xml", xml, None).encoding, WINDOWS_1252);
+
+    // Meta tags are ignored for non-markup types, and UTF-16 declarations fall back to UTF-8.
+    assert_eq!(Charset::detect("text/plain", b"<meta charset=latin1>", None), Charset::default());
+    assert_eq!(Charset::detect("text/html", b"<meta charset=utf-16>", None).encoding, UTF_8);
+}
+
+#[test]
+fn override_beats_everything() {
+    let cs = Charset::detect("text/plain; charset=utf-8", b"\xef\xbb\xbfabc", Some(WINDOWS_1252));
+    assert_eq!(cs.source, CharsetSource::Override);
+    assert_eq!(cs.to_string(), "windows-1252 (--encoding)");
+}
+
+#[test]
+fn encoding_labels_from_cli() {
+    let cli = Cli::try_parse_from(["rr", "get", "http://x", "--encoding", "latin1"]).unwrap();
+    assert_eq!(charset::from_cli(&cli).unwrap(), Some(WINDOWS_1252));
+
+    let cli = Cli::try_parse_from(["rr", "get", "http://x", "--encoding", "klingon"]).unwrap();
+    assert!(charset::from_cli(&cli).is_err());
+
+    let cli = Cli::try_parse_from(["rr", "get", "http://x"]).unwrap();
+    assert_eq!(charset::from_cli(&cli).unwrap(), None);
+}
@@ -0,0 +1,29 @@
+use clap::{CommandFactory, Parser};
+use rustrest::cli::{Cli, CollectionAction, Command, OutputFormat};
+
+#[test]
+fn cli_definition_is_consistent() {
+    Cli::command().debug_assert();
+}
+
+#[test]
+fn short_q_and_f_are_the_global_flags() {
+    let cli = Cli::try_parse_from(["rr", "get", "http://x", "-q", "-f", "json", "--query", "page=1"]).unwrap();
+    assert!(cli.quiet);
+    assert_eq!(cli.format, OutputFormat::Json);
+    let Command::Get { query, .. } = cli.command else { panic!("expected get") };
+    assert_eq!(query, ["page=1"]);
+}
+
+#[test]
+fn collection_run_accepts_all() {
+    let cli = Cli::try_parse_from(["rr", "collection", "run", "smoke", "--all"]).unwrap();
+    let Command::Collection { action: CollectionAction::Run { collection, request, all, .. } } = cli.command else {
+        panic!("expected collection run");
+    };
+    assert_eq!(collection, "smoke");
+    assert_eq!(request, None);
+    assert!(all);
+
+    assert!(Cli::try_parse_from(["rr", "collection", "run", "smoke", "login", "--all"]).is_err());
+}
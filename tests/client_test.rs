@@ -9,6 +9,7 @@ fn request_stores_method_and_url() {
         headers: HashMap::new(),
         query:   HashMap::new(),
         body:    None,
+        auth:    None,
     };
     assert_eq!(req.url, "https://httpbin.org/get");
     assert!(req.headers.is_empty());
@@ -37,6 +38,7 @@ fn request_with_headers() {
         headers,
         query:   HashMap::new(),
         body:    None,
+        auth:    None,
     };
 
     assert_eq!(req.headers.get("Authorization").unwrap(), "Bearer token123");
@@ -0,0 +1,125 @@
+use rustrest::collections::Collection;
+
+fn collection(json: &str) -> Collection {
+    serde_json::from_str(json).expect("valid collection")
+}
+
+const REQUESTS: &str = r#"{
+    "zeta":  { "name": "Z", "description": null, "request": { "method": "GET", "url": "http://x/z", "headers": {}, "query": {}, "body": null } },
+    "alpha": { "name": "A", "description": null, "request": { "method": "GET", "url": "http://x/a", "headers": {}, "query": {}, "body": null } },
+    "login": { "name": "L", "description": null, "request": { "method": "POST", "url": "http://x/l", "headers": {}, "query": {}, "body": null } }
+}"#;
+
+#[test]
+fn ordered_requests_without_order_is_alphabetical() {
+    let col = collection(&format!(
+        r#"{{ "name": "c", "description": null, "requests": {REQUESTS} }}"#
+    ));
+    let keys: Vec<_> = col.ordered_requests().into_iter().map(|(k, _)| k).collect();
+    assert_eq!(keys, ["alpha", "login", "zeta"]);
+}
+
+#[test]
+fn ordered_requests_honours_explicit_order_first() {
+    let col = collection(&format!(
+        r#"{{ "name": "c", "description": null, "order": ["login", "zeta", "missing"], "requests": {REQUESTS} }}"#
+    ));
+    let keys: Vec<_> = col.ordered_requests().into_iter().map(|(k, _)| k).collect();
+    assert_eq!(keys, ["login", "zeta", "alpha"]);
+}
+
+#[test]
+fn project_collections_are_listed_and_found_before_global_ones() {
+    use rustrest::collections::{find, list_all, CollectionOrigin};
+    use rustrest::config::AppConfig;
+
+    let root = std::env::temp_dir().join(format!("rustrest-cols-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    let global = root.join("global");
+    let project = root.join(".rustrest");
+    std::fs::create_dir_all(&global).unwrap();
+    std::fs::create_dir_all(project.join("collections")).unwrap();
+    std::fs::write(global.join("shared.json"), "{}").unwrap();
+    std::fs::write(global.join("personal.json"), "{}").unwrap();
+    std::fs::write(project.join("collections").join("shared.json"), "{}").unwrap();
+
+    let cfg = AppConfig {
+        collections_dir: global,
+        project_dir: Some(project.clone()),
+        ..AppConfig::default()
+    };
+
+    let all = list_all(&cfg).unwrap();
+    assert_eq!(
+        all,
+        [
+            ("shared".to_string(), CollectionOrigin::Project),
+            ("personal".to_string(), CollectionOrigin::Global),
+            ("shared".to_string(), CollectionOrigin::Global),
+        ]
+    );
+    let (origin, path) = find(&cfg, "shared").unwrap();
+    assert_eq!(origin, CollectionOrigin::Project);
+    assert_eq!(path, project.join("collections").join("shared.json"));
+}
+
+#[test]
+fn validate_rejects_unknown_and_duplicate_order_entries() {
+    let ok = collection(&format!(r#"{{ "name": "c", "description": null, "order": ["login", "zeta"], "requests": {REQUESTS} }}"#));
+    assert!(ok.validate().is_ok());
+
+    let typo = collection(&format!(r#"{{ "name": "c", "description": null, "order": ["logn"], "requests": {REQUESTS} }}"#));
+    assert!(typo.validate().unwrap_err().to_string().contains("'logn' names a request that does not exist"));
+
+    let twice = collection(&format!(r#"{{ "name": "c", "description": null, "order": ["zeta", "alpha", "zeta"], "requests": {REQUESTS} }}"#));
+    assert!(twice.validate().unwrap_err().to_string().contains("'zeta' lists a request more than once"));
+}
+
+/// Write a one-request `smoke` collection for `url` under a fresh config dir.
+fn smoke_dir(tag: &str, url: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("rustrest-{tag}-{}", std::process::id()));
+    let collections = dir.join("rustrest").join("collections");
+    std::fs::create_dir_all(&collections).unwrap();
+    std::fs::write(
+        collections.join("smoke.json"),
+        format!(
+            r#"{{ "name": "smoke", "description": null, "requests": {{
+                "a": {{ "name": "A", "description": null, "request": {{ "method": "GET", "url": "{url}", "headers": {{}}, "query": {{}}, "body": null }} }}
+            }} }}"#
+        ),
+    )
+    .unwrap();
+    dir
+}
+
+fn rr(dir: &std::path::Path, args: &[&str]) -> std::process::Output {
+    std::process::Command::new(env!("CARGO_BIN_EXE_rr"))
+        .args(args)
+        .current_dir(dir)
+        .env("XDG_CONFIG_HOME", dir)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn quiet_collection_run_prints_nothing() {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/a").with_body("{}").create();
+
+    let dir = smoke_dir("quiet-run", &format!("{}/a", server.url()));
+    let out = rr(&dir, &["-q", "collection", "run", "smoke"]);
+    assert!(out.status.success());
+    assert_eq!(String::from_utf8_lossy(&out.stdout), "");
+    let _ = std::fs::remove_dir_all(dir);
+}
+
+#[test]
+fn error_status_fails_single_and_whole_runs_alike() {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/a").with_status(500).create();
+
+    let dir = smoke_dir("status-rule", &format!("{}/a", server.url()));
+    assert_eq!(rr(&dir, &["-q", "collection", "run", "smoke", "a"]).status.code(), Some(2));
+    assert_eq!(rr(&dir, &["-q", "collection", "run", "smoke"]).status.code(), Some(2));
+    let _ = std::fs::remove_dir_all(dir);
+}
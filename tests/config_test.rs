@@ -0,0 +1,137 @@
+use rustrest::config::AppConfig;
+use rustrest::error::AppError;
+
+#[test]
+fn set_key_parses_typed_values() {
+    let mut cfg = AppConfig::default();
+    cfg.set_key("timeout_secs", "5").unwrap();
+    cfg.set_key("follow_redirects", "off").unwrap();
+    cfg.set_key("max_history", "10").unwrap();
+    cfg.set_key("base_url", "https://api.example.com").unwrap();
+    cfg.set_key("default_headers.X-Api-Key", "secret").unwrap();
+
+    assert_eq!(cfg.timeout_secs, 5);
+    assert!(!cfg.follow_redirects);
+    assert_eq!(cfg.max_history, 10);
+    assert_eq!(cfg.base_url.as_deref(), Some("https://api.example.com"));
+    assert_eq!(cfg.get_key("default_headers.X-Api-Key").unwrap().as_deref(), Some("secret"));
+}
+
+#[test]
+fn set_key_rejects_unknown_keys_and_bad_values() {
+    let mut cfg = AppConfig::default();
+    assert!(matches!(cfg.set_key("colour", "red"), Err(AppError::Config(_))));
+    assert!(matches!(cfg.set_key("timeout_secs", "soon"), Err(AppError::Config(_))));
+    assert!(matches!(cfg.set_key("follow_redirects", "maybe"), Err(AppError::Config(_))));
+    assert!(matches!(cfg.set_key("base_url", "not a url"), Err(AppError::Config(_))));
+    assert!(matches!(cfg.get_key("nope"), Err(AppError::Config(_))));
+}
+
+#[test]
+fn unset_key_restores_defaults() {
+    let mut cfg = AppConfig::default();
+    cfg.set_key("timeout_secs", "5").unwrap();
+    cfg.set_key("default_headers.Accept", "text/plain").unwrap();
+
+    cfg.unset_key("timeout_secs").unwrap();
+    cfg.unset_key("default_headers.Accept").unwrap();
+
+    assert_eq!(cfg.timeout_secs, AppConfig::default().timeout_secs);
+    assert!(cfg.default_headers.is_empty());
+    assert_eq!(cfg.get_key("base_url").unwrap(), None);
+}
+
+fn scratch_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("rustrest-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn project_config_overlays_global_config() {
+    let root = scratch_dir("overlay");
+    let global = root.join("global.json");
+    std::fs::write(
+        &global,
+        r#"{ "timeout_secs": 5, "base_url": "https://global.test", "default_headers": { "A": "1", "B": "2" } }"#,
+    )
+    .unwrap();
+
+    let project = root.join(".rustrest");
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::write(
+        project.join("config.json"),
+        r#"{ "base_url": "http://localhost:3000", "default_headers": { "B": "project" } }"#,
+    )
+    .unwrap();
+
+    let cfg = AppConfig::load_from(&global, Some(&project)).unwrap();
+    assert_eq!(cfg.timeout_secs, 5);
+    assert_eq!(cfg.base_url.as_deref(), Some("http://localhost:3000"));
+    assert_eq!(cfg.default_headers["A"], "1");
+    assert_eq!(cfg.default_headers["B"], "project");
+    assert_eq!(cfg.project_collections_dir(), Some(project.join("collections")));
+}
+
+#[test]
+fn project_dir_is_found_from_nested_directories() {
+    let root = scratch_dir("discover");
+    std::fs::create_dir_all(root.join(".rustrest")).unwrap();
+    let nested = root.join("src").join("api");
+    std::fs::create_dir_all(&nested).unwrap();
+
+    assert_eq!(AppConfig::find_project_dir(&nested), Some(root.join(".rustrest")));
+}
+
+fn with_profiles() -> AppConfig {
+    serde_json::from_value(serde_json::json!({
+        "base_url": "https://prod.example.com",
+        "default_headers": { "X-Api-Key": "prod", "Accept": "application/json" },
+        "timeout_secs": 30,
+        "profiles": {
+            "local": {
+                "base_url": "http://localhost:3000",
+                "default_headers": { "X-Api-Key": "dev" },
+                "timeout_secs": 2
+            }
+        }
+    }))
+    .unwrap()
+}
+
+#[test]
+fn profile_switches_base_url_and_headers_as_a_unit() {
+    let cfg = with_profiles().with_profile(Some("local")).unwrap();
+    assert_eq!(cfg.base_url.as_deref(), Some("http://localhost:3000"));
+    assert_eq!(cfg.timeout_secs, 2);
+    assert_eq!(cfg.default_headers.len(), 1);
+    assert_eq!(cfg.default_headers["X-Api-Key"], "dev");
+    assert_eq!(cfg.active_profile.as_deref(), Some("local"));
+}
+
+#[test]
+fn default_profile_applies_when_none_is_given() {
+    let mut cfg = with_profiles();
+    assert_eq!(cfg.clone().with_profile(None).unwrap().base_url.as_deref(), Some("https://prod.example.com"));
+
+    cfg.set_key("default_profile", "local").unwrap();
+    assert_eq!(cfg.with_profile(None).unwrap().timeout_secs, 2);
+}
+
+#[test]
+fn unknown_profiles_are_rejected() {
+    assert!(matches!(with_profiles().with_profile(Some("staging")), Err(AppError::Config(_))));
+    assert!(matches!(with_profiles().set_key("default_profile", "staging"), Err(AppError::Config(_))));
+}
+
+#[test]
+fn unknown_default_profile_is_ignored() {
+    let mut cfg = with_profiles();
+    cfg.default_profile = Some("project-only".to_string());
+
+    let applied = cfg.clone().with_profile(None).unwrap();
+    assert_eq!(applied.base_url.as_deref(), Some("https://prod.example.com"));
+    assert_eq!(applied.active_profile, None);
+    assert!(matches!(cfg.with_profile(Some("project-only")), Err(AppError::Config(_))));
+}
@@ -0,0 +1,128 @@
+use rustrest::auth::Auth;
+use rustrest::client::HttpClient;
+use rustrest::config::AppConfig;
+use rustrest::digest::{Algorithm, Challenge};
+use rustrest::request::{HttpMethod, HttpRequest};
+use std::collections::HashMap;
+
+fn response_of(header: &str) -> &str {
+    let start = header.find("response=\"").unwrap() + "response=\"".len();
+    &header[start..start + header[start..].find('"').unwrap()]
+}
+
+#[test]
+fn parses_challenge_parameters() {
+    let challenge = Challenge::parse(
+        r#"Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm=SHA-256, nonce="abc", opaque="xyz""#,
+    )
+    .unwrap();
+    assert_eq!(challenge.realm, "http-auth@example.org");
+    assert_eq!(challenge.nonce, "abc");
+    assert_eq!(challenge.opaque.as_deref(), Some("xyz"));
+    assert_eq!(challenge.algorithm, Algorithm::Sha256);
+    assert!(challenge.qop_auth);
+
+    assert!(Challenge::parse(r#"Basic realm="x""#).is_err());
+    assert!(Challenge::parse(r#"Digestive realm="x", nonce="n""#).is_err());
+    assert_eq!(Challenge::parse(r#"DIGEST realm="x", nonce="n""#).unwrap().nonce, "n");
+    assert!(Challenge::parse(r#"Digest realm="x", nonce="n", qop="auth-int""#).is_err());
+}
+
+#[test]
+fn computes_rfc_7616_responses() {
+    let mut challenge = Challenge::parse(
+        r#"Digest realm="http-auth@example.org", qop="auth", algorithm=MD5, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#,
+    )
+    .unwrap();
+    let cnonce = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+
+    let md5 = challenge.authorization("Mufasa", "Circle of Life", "GET", "/dir/index.html", 1, cnonce);
+    assert_eq!(response_of(&md5), "8ca523f5e9506fed4657c9700eebdbec");
+    assert!(md5.contains("nc=00000001"));
+
+    challenge.algorithm = Algorithm::Sha256;
+    let sha = challenge.authorization("Mufasa", "Circle of Life", "GET", "/dir/index.html", 1, cnonce);
+    assert_eq!(response_of(&sha), "753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1");
+}
+
+#[tokio::test]
+async fn send_answers_challenge_and_counts_nonces() {
+    let mut server = mockito::Server::new_async().await;
+    let challenge = server
+        .mock("GET", "/status")
+        .match_header("authorization", mockito::Matcher::Missing)
+        .with_status(401)
+        .with_header("www-authenticate", r#"Digest realm="appliance", qop="auth", nonce="n1""#)
+        .expect(1)
+        .create_async()
+        .await;
+    let first = server
+        .mock("GET", "/status")
+        .match_header("authorization", mockito::Matcher::Regex(r#"^Digest username="admin".*nc=00000001"#.into()))
+        .with_status(200)
+        .expect(1)
+        .create_async()
+        .await;
+    let second = server
+        .mock("GET", "/status")
+        .match_header("authorization", mockito::Matcher::Regex("nc=00000002".into()))
+        .with_status(200)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let http = HttpClient::new(&AppConfig::default()).unwrap();
+    for _ in 0..2 {
+        let req = HttpRequest {
+            method:  HttpMethod::Get,
+            url:     format!("{}/status", server.url()),
+            headers: HashMap::new(),
+            query:   HashMap::new(),
+            body:    None,
+            auth:    Some(Auth::Digest { username: "admin".to_string(), password: Some("pw".to_string()) }),
+        };
+        let resp = http.send(req).await.unwrap();
+        assert_eq!(resp.status().as_u16(), 200);
+    }
+
+    challenge.assert_async().await;
+    first.assert_async().await;
+    second.assert_async().await;
+}
+
+/// The retry after the server's challenge must resend a body read from stdin.
+#[test]
+fn stdin_body_survives_digest_retry() {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut server = mockito::Server::new();
+    let challenge = server
+        .mock("POST", "/upload")
+        .match_header("authorization", mockito::Matcher::Missing)
+        .with_status(401)
+        .with_header("www-authenticate", r#"Digest realm="r", qop="auth", nonce="n1""#)
+        .create();
+    let answered = server
+        .mock("POST", "/upload")
+        .match_header("authorization", mockito::Matcher::Regex("^Digest ".into()))
+        .match_body("hello world")
+        .with_status(200)
+        .create();
+
+    let config = std::env::temp_dir().join(format!("rustrest-digest-stdin-{}", std::process::id()));
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rr"))
+        .args(["-q", "post", &format!("{}/upload", server.url()), "--auth", "u:p", "--digest", "--body", "@-"])
+        .env("XDG_CONFIG_HOME", &config)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"hello world").unwrap();
+    let status = child.wait().unwrap();
+
+    challenge.assert();
+    answered.assert();
+    assert!(status.success());
+    let _ = std::fs::remove_dir_all(config);
+}
@@ -41,3 +41,12 @@ fn content_type_json_detection() {
     assert!(!is_json("text/html"));
     assert!(!is_json("application/xml"));
 }
+
+#[test]
+fn hexdump_rows_show_offset_hex_and_ascii() {
+    let dump = rustrest::display::hexdump(b"\x89PNG\r\n\x1a\n0123456789abcdefXY");
+    let rows: Vec<&str> = dump.lines().collect();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0], "00000000  89 50 4e 47 0d 0a 1a 0a  30 31 32 33 34 35 36 37  |.PNG....01234567|");
+    assert_eq!(rows[1], "00000010  38 39 61 62 63 64 65 66  58 59                    |89abcdefXY|");
+}
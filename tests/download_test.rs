@@ -0,0 +1,156 @@
+use clap::Parser;
+use rustrest::cli::Cli;
+use rustrest::client::HttpClient;
+use rustrest::config::AppConfig;
+use rustrest::download::{self, DownloadOptions};
+use rustrest::request::{HttpMethod, HttpRequest};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+fn get(url: String) -> HttpRequest {
+    HttpRequest {
+        method:  HttpMethod::Get,
+        url,
+        headers: HashMap::new(),
+        query:   HashMap::new(),
+        body:    None,
+        auth:    None,
+    }
+}
+
+fn target(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rustrest-dl-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+/// Downloads are recorded in history; keep that out of the real config dir.
+fn config() -> AppConfig {
+    static ISOLATE: std::sync::Once = std::sync::Once::new();
+    ISOLATE.call_once(|| {
+        let dir = std::env::temp_dir().join(format!("rustrest-dl-config-{}", std::process::id()));
+        std::env::set_var("XDG_CONFIG_HOME", dir);
+    });
+    AppConfig::default()
+}
+
+#[test]
+fn filename_from_content_disposition() {
+    assert_eq!(download::disposition_filename(r#"attachment; filename="report.pdf""#).as_deref(), Some("report.pdf"));
+    assert_eq!(
+        download::disposition_filename("attachment; filename=x.bin; filename*=UTF-8''na%C3%AFve%20file.txt").as_deref(),
+        Some("naïve file.txt")
+    );
+    assert_eq!(download::disposition_filename(r#"attachment; filename="../../etc/passwd""#).as_deref(), Some("passwd"));
+    assert_eq!(download::disposition_filename("inline"), None);
+    assert_eq!(download::disposition_filename("attachment; filename*=UTF-8''a&b=c%2Bd.txt").as_deref(), Some("a&b=c+d.txt"));
+}
+
+#[test]
+fn filename_from_url() {
+    assert_eq!(download::url_filename("http://x/files/a&b=c.txt?v=1"), "a&b=c.txt");
+    assert_eq!(download::url_filename("http://x/files/na%C3%AFve%20file+1.txt"), "naïve file+1.txt");
+    assert_eq!(download::url_filename("http://x/"), "download");
+}
+
+#[test]
+fn cli_selects_download_mode() {
+    let cli = Cli::try_parse_from(["rr", "get", "http://x/a.bin", "-o", "out.bin", "--resume"]).unwrap();
+    assert!(cli.validate().is_ok());
+    let opts = DownloadOptions::from_cli(&cli).unwrap();
+    assert_eq!(opts.output, Some(PathBuf::from("out.bin")));
+    assert!(opts.resume);
+
+    let cli = Cli::try_parse_from(["rr", "get", "http://x/a.bin"]).unwrap();
+    assert!(DownloadOptions::from_cli(&cli).is_none());
+
+    // `--resume` needs a file to resume, `--force` a name to overwrite.
+    let cli = Cli::try_parse_from(["rr", "get", "http://x/a.bin", "--resume"]).unwrap();
+    assert!(cli.validate().is_err());
+    let cli = Cli::try_parse_from(["rr", "get", "http://x/a.bin", "--download", "--resume"]).unwrap();
+    assert!(cli.validate().is_err());
+    assert!(Cli::try_parse_from(["rr", "get", "http://x/a.bin", "--force"]).is_err());
+    assert!(Cli::try_parse_from(["rr", "get", "http://x/a.bin", "--download", "--force"]).is_ok());
+}
+
+#[tokio::test]
+async fn streams_body_to_output_file() {
+    let body: Vec<u8> = (0..=255u8).cycle().take(100_000).collect();
+    let mut server = mockito::Server::new_async().await;
+    server.mock("GET", "/file.bin").with_body(&body).create_async().await;
+
+    let path = target("full");
+    let cli  = Cli::try_parse_from(["rr", "-q", "get", "http://x"]).unwrap();
+    let opts = DownloadOptions { output: Some(path.clone()), resume: false, force: false };
+    let config = config();
+    let http = HttpClient::new(&config).unwrap();
+    let written = download::download(&http, &get(format!("{}/file.bin", server.url())), &opts, &cli, &config)
+        .await
+        .unwrap();
+
+    assert_eq!(written, path);
+    assert_eq!(std::fs::read(&path).unwrap(), body);
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn resumes_partial_file_with_range() {
+    let mut server = mockito::Server::new_async().await;
+    let ranged = server
+        .mock("GET", "/file.txt")
+        .match_header("range", "bytes=6-")
+        .with_status(206)
+        .with_body("world")
+        .create_async()
+        .await;
+
+    let path = target("resume");
+    std::fs::write(&path, "hello ").unwrap();
+    let cli  = Cli::try_parse_from(["rr", "-q", "get", "http://x"]).unwrap();
+    let opts = DownloadOptions { output: Some(path.clone()), resume: true, force: false };
+    let config = config();
+    let http = HttpClient::new(&config).unwrap();
+    let req  = get(format!("{}/file.txt", server.url()));
+    download::download(&http, &req, &opts, &cli, &config).await.unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "hello world");
+    ranged.assert_async().await;
+
+    // Nothing left to fetch: the file is kept as is.
+    server
+        .mock("GET", "/file.txt")
+        .match_header("range", "bytes=11-")
+        .with_status(416)
+        .create_async()
+        .await;
+    download::download(&http, &req, &opts, &cli, &config).await.unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "hello world");
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn download_refuses_to_overwrite_without_force() {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/report.txt").with_body("new").create();
+
+    let dir = std::env::temp_dir().join(format!("rustrest-dl-keep-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("report.txt"), "old").unwrap();
+    let run = |extra: &[&str]| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_rr"))
+            .args(["-q", "get", &format!("{}/report.txt", server.url()), "--download"])
+            .args(extra)
+            .current_dir(&dir)
+            .env("XDG_CONFIG_HOME", dir.join("config"))
+            .output()
+            .unwrap()
+    };
+
+    let refused = run(&[]);
+    assert!(!refused.status.success());
+    assert!(String::from_utf8_lossy(&refused.stderr).contains("already exists"));
+    assert_eq!(std::fs::read_to_string(dir.join("report.txt")).unwrap(), "old");
+
+    assert!(run(&["--force"]).status.success());
+    assert_eq!(std::fs::read_to_string(dir.join("report.txt")).unwrap(), "new");
+    let _ = std::fs::remove_dir_all(dir);
+}
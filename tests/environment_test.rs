@@ -0,0 +1,71 @@
+use rustrest::environment::{expand_request, substitute, Variables};
+use rustrest::error::AppError;
+use rustrest::request::{HttpMethod, HttpRequest, RequestBody};
+use std::collections::HashMap;
+
+fn vars() -> Variables {
+    let mut v = Variables::new();
+    v.insert("host".to_string(), "https://staging.example.com".to_string());
+    v.insert("token".to_string(), "abc123".to_string());
+    v.insert("id".to_string(), "42".to_string());
+    v
+}
+
+#[test]
+fn substitute_replaces_placeholders() {
+    let out = substitute("{{host}}/users/{{ id }}", &vars()).unwrap();
+    assert_eq!(out, "https://staging.example.com/users/42");
+}
+
+#[test]
+fn substitute_leaves_plain_text_alone() {
+    assert_eq!(substitute("no vars {here}", &vars()).unwrap(), "no vars {here}");
+}
+
+#[test]
+fn substitute_reports_missing_variable() {
+    match substitute("{{host}}/{{missing}}", &vars()) {
+        Err(AppError::UnresolvedVariable { name }) => assert_eq!(name, "missing"),
+        other => panic!("expected UnresolvedVariable, got {other:?}"),
+    }
+}
+
+#[test]
+fn expand_request_covers_url_headers_query_and_body() {
+    let mut headers = HashMap::new();
+    headers.insert("Authorization".to_string(), "Bearer {{token}}".to_string());
+    let mut query = HashMap::new();
+    query.insert("user".to_string(), "{{id}}".to_string());
+
+    let req = HttpRequest {
+        method:  HttpMethod::Post,
+        url:     "{{host}}/users".to_string(),
+        headers,
+        query,
+        body:    Some(RequestBody::Json(serde_json::json!({ "ids": ["{{id}}"], "n": 1 }))),
+        auth:    None,
+    };
+
+    let out = expand_request(&req, &vars(), None).unwrap();
+    assert_eq!(out.url, "https://staging.example.com/users");
+    assert_eq!(out.headers["Authorization"], "Bearer abc123");
+    assert_eq!(out.query["user"], "42");
+    match out.body {
+        Some(RequestBody::Json(v)) => assert_eq!(v, serde_json::json!({ "ids": ["42"], "n": 1 })),
+        other => panic!("unexpected body {other:?}"),
+    }
+}
+
+#[test]
+fn expand_request_resolves_relative_url_against_base() {
+    let req = HttpRequest {
+        method:  HttpMethod::Get,
+        url:     "/users/{{id}}".to_string(),
+        headers: HashMap::new(),
+        query:   HashMap::new(),
+        body:    None,
+        auth:    None,
+    };
+    let out = expand_request(&req, &vars(), Some("http://localhost:3000")).unwrap();
+    assert_eq!(out.url, "http://localhost:3000/users/42");
+}
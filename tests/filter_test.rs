@@ -0,0 +1,104 @@
+use rustrest::error::AppError;
+use rustrest::filter::{self, Filter};
+use serde_json::{json, Value};
+
+fn run(expr: &str, input: &Value) -> Vec<Value> {
+    Filter::parse(expr).unwrap().eval(input).unwrap()
+}
+
+fn sample() -> Value {
+    json!({
+        "total": 3,
+        "items": [
+            { "id": 1, "name": "alpha", "active": true,  "tags": ["a", "b"] },
+            { "id": 2, "name": "beta",  "active": false, "tags": [] },
+            { "id": 3, "name": "gamma", "active": true,  "tags": ["c"] }
+        ],
+        "meta data": { "page": 1 }
+    })
+}
+
+#[test]
+fn selects_paths_and_indices() {
+    let v = sample();
+    assert_eq!(run(".", &v), vec![v.clone()]);
+    assert_eq!(run(".total", &v), vec![json!(3)]);
+    assert_eq!(run(".items[0].name", &v), vec![json!("alpha")]);
+    assert_eq!(run(".items[-1].id", &v), vec![json!(3)]);
+    assert_eq!(run(".items[9]", &v), vec![Value::Null]);
+    assert_eq!(run(".missing.deeper", &v), vec![Value::Null]);
+    assert_eq!(run(r#"."meta data".page"#, &v), vec![json!(1)]);
+    assert_eq!(run(r#".["meta data"]"#, &v), vec![json!({ "page": 1 })]);
+}
+
+#[test]
+fn slices_arrays_and_strings() {
+    let v = sample();
+    assert_eq!(run(".items[1:] | length", &v), vec![json!(2)]);
+    assert_eq!(run(".items[:1][].id", &v), vec![json!(1)]);
+    assert_eq!(run(".items[-2:-1][].id", &v), vec![json!(2)]);
+    assert_eq!(run(".items[0].name[1:3]", &v), vec![json!("lp")]);
+}
+
+#[test]
+fn wildcards_and_pipes_stream_outputs() {
+    let v = sample();
+    let ids = vec![json!(1), json!(2), json!(3)];
+    assert_eq!(run(".items[].id", &v), ids);
+    assert_eq!(run(".items[*].id", &v), ids);
+    assert_eq!(run(".items | .[] | .id", &v), ids);
+    assert_eq!(run("[.items[].tags[]]", &v), vec![json!(["a", "b", "c"])]);
+    assert_eq!(run(".items | map(.name)", &v), vec![json!(["alpha", "beta", "gamma"])]);
+}
+
+#[test]
+fn select_filters_with_comparisons() {
+    let v = sample();
+    assert_eq!(run(".items[] | select(.active) | .id", &v), vec![json!(1), json!(3)]);
+    assert_eq!(run(r#".items[] | select(.name == "beta") | .id"#, &v), vec![json!(2)]);
+    assert_eq!(run(".items[] | select(.id >= 2 and .active) | .name", &v), vec![json!("gamma")]);
+    assert_eq!(run(".items[] | select(.id < 2 or (.tags | length) == 0) | .id", &v), vec![json!(1), json!(2)]);
+    assert_eq!(run("[.items[] | select(.active | not)] | length", &v), vec![json!(1)]);
+}
+
+#[test]
+fn length_and_keys() {
+    let v = sample();
+    assert_eq!(run("keys", &v), vec![json!(["items", "meta data", "total"])]);
+    assert_eq!(run(".items | keys", &v), vec![json!([0, 1, 2])]);
+    assert_eq!(run(".items[0].name | length", &v), vec![json!(5)]);
+    assert_eq!(run(".nothing | length", &v), vec![json!(0)]);
+}
+
+#[test]
+fn apply_parses_the_body() {
+    let out = filter::apply(r#"{"data":{"token":"t"}}"#, ".data.token").unwrap();
+    assert_eq!(out, vec![json!("t")]);
+
+    let err = filter::apply("<html>", ".data").unwrap_err();
+    assert!(matches!(err, AppError::Filter(ref m) if m.contains("not JSON")));
+}
+
+#[test]
+fn number_literals_keep_their_type() {
+    let v = sample();
+    assert_eq!(run("1", &v), vec![json!(1)]);
+    assert_eq!(run("-2", &v), vec![json!(-2)]);
+    assert_eq!(run("1.5", &v), vec![json!(1.5)]);
+    assert_eq!(run("1e3", &v), vec![json!(1000.0)]);
+    assert_eq!(run("[.items[] | select(.id == 2) | 1]", &v), vec![json!([1])]);
+    assert_eq!(filter::apply("{}", "1").unwrap()[0].to_string(), "1");
+}
+
+#[test]
+fn rejects_malformed_expressions() {
+    for expr in [".items[", ".a |", "select(.x", "frobnicate", ".[1.5]", "\"open"] {
+        assert!(matches!(Filter::parse(expr), Err(AppError::Filter(_))), "{expr} should be rejected");
+    }
+}
+
+#[test]
+fn type_errors_are_reported() {
+    let err = Filter::parse(".total.x").unwrap().eval(&sample()).unwrap_err();
+    assert!(err.to_string().contains("cannot index number"));
+}
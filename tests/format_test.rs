@@ -0,0 +1,99 @@
+use rustrest::format::{self, formatter_for};
+
+fn pretty(body: &str, content_type: &str) -> Option<String> {
+    colored::control::set_override(false);
+    format::pretty(body, content_type)
+}
+
+#[test]
+fn registry_matches_media_types() {
+    let name = |ct| formatter_for(ct).map(|f| f.name);
+    assert_eq!(name("application/json; charset=utf-8"), Some("json"));
+    assert_eq!(name("application/problem+json"), Some("json"));
+    assert_eq!(name("application/soap+xml"), Some("xml"));
+    assert_eq!(name("Text/XML"), Some("xml"));
+    assert_eq!(name("text/html; charset=iso-8859-1"), Some("html"));
+    assert_eq!(name("application/x-yaml"), Some("yaml"));
+    assert_eq!(name("application/x-www-form-urlencoded"), Some("form"));
+    assert_eq!(name("text/csv"), Some("csv"));
+    assert_eq!(name("text/plain"), None);
+}
+
+#[test]
+fn xml_is_indented_one_element_per_line() {
+    let body = r#"<?xml version="1.0"?><env:Envelope xmlns:env="urn:x"><env:Body><r id="1"><name>Alice</name><empty/><!-- note --></r></env:Body></env:Envelope>"#;
+    let expected = [
+        r#"<?xml version="1.0"?>"#,
+        r#"<env:Envelope xmlns:env="urn:x">"#,
+        "  <env:Body>",
+        r#"    <r id="1">"#,
+        "      <name>Alice</name>",
+        "      <empty/>",
+        "      <!-- note -->",
+        "    </r>",
+        "  </env:Body>",
+        "</env:Envelope>",
+    ]
+    .join("\n");
+    assert_eq!(pretty(body, "application/soap+xml").unwrap(), expected);
+}
+
+#[test]
+fn malformed_xml_falls_back() {
+    assert_eq!(pretty("<a><b></a></b>", "application/xml"), None);
+    assert_eq!(pretty("<a><b>", "application/xml"), None);
+    assert_eq!(pretty("just text", "text/xml"), None);
+    assert_eq!(pretty("<a attr=\"unterminated></a>", "text/xml"), None);
+}
+
+#[test]
+fn html_tolerates_void_unclosed_and_raw_elements() {
+    let body = "<!DOCTYPE html><html><head><meta charset=utf-8><script>if (a < b) {}</script></head><body><p>Hi <b>there</b><br>bye</body></html>";
+    let expected = [
+        "<!DOCTYPE html>",
+        "<html>",
+        "  <head>",
+        "    <meta charset=utf-8>",
+        "    <script>",
+        "if (a < b) {}",
+        "    </script>",
+        "  </head>",
+        "  <body>",
+        "    <p>",
+        "      Hi",
+        "      <b>there</b>",
+        "      <br>",
+        "      bye",
+        "  </body>",
+        "</html>",
+    ]
+    .join("\n");
+    assert_eq!(pretty(body, "text/html").unwrap(), expected);
+}
+
+#[test]
+fn yaml_keeps_structure() {
+    let body = "name: app  # comment\nitems:\n  - id: 1\n  - \"a: b\"\n";
+    assert_eq!(pretty(body, "application/yaml").unwrap(), body.trim_end());
+}
+
+#[test]
+fn form_bodies_are_decoded_and_aligned() {
+    let out = pretty("name=Alice+Smith&city=S%C3%A3o%20Paulo&x=", "application/x-www-form-urlencoded").unwrap();
+    assert_eq!(out, "name = Alice Smith\ncity = São Paulo\nx    = ");
+    assert_eq!(pretty("   ", "application/x-www-form-urlencoded"), None);
+}
+
+#[test]
+fn csv_renders_as_table() {
+    let out = pretty("id,name,note\r\n1,Alice,\"says \"\"hi\"\"\"\r\n2,Bob\r\n", "text/csv").unwrap();
+    let expected = [
+        "id │ name  │ note",
+        "───┼───────┼──────────",
+        "1  │ Alice │ says \"hi\"",
+        "2  │ Bob   │",
+    ]
+    .join("\n");
+    assert_eq!(out, expected);
+    assert_eq!(pretty("a,\"open\n", "text/csv"), None);
+}
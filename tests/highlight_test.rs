@@ -0,0 +1,50 @@
+use rustrest::highlight::{self, Token, TokenKind};
+
+fn kinds(text: &str) -> Vec<(TokenKind, &str)> {
+    highlight::tokens(text)
+        .into_iter()
+        .filter(|t| t.kind != TokenKind::Whitespace)
+        .map(|Token { kind, text }| (kind, text))
+        .collect()
+}
+
+#[test]
+fn classifies_every_value_type() {
+    use TokenKind::*;
+    let json = r#"{"name": "a\"b", "n": -1.5e3, "ok": true, "gone": null, "list": ["s", 2, false]}"#;
+    assert_eq!(kinds(json), vec![
+        (Punctuation, "{"),
+        (Key, r#""name""#), (Punctuation, ":"), (String, r#""a\"b""#), (Punctuation, ","),
+        (Key, r#""n""#), (Punctuation, ":"), (Number, "-1.5e3"), (Punctuation, ","),
+        (Key, r#""ok""#), (Punctuation, ":"), (Boolean, "true"), (Punctuation, ","),
+        (Key, r#""gone""#), (Punctuation, ":"), (Null, "null"), (Punctuation, ","),
+        (Key, r#""list""#), (Punctuation, ":"), (Punctuation, "["),
+        (String, r#""s""#), (Punctuation, ","), (Number, "2"), (Punctuation, ","), (Boolean, "false"),
+        (Punctuation, "]"),
+        (Punctuation, "}"),
+    ]);
+}
+
+#[test]
+fn keys_containing_colons_and_string_array_elements() {
+    let pretty = "{\n  \"a\\\": b\": [\n    \"x: y\"\n  ]\n}";
+    let k = kinds(pretty);
+    assert_eq!(k[1], (TokenKind::Key, "\"a\\\": b\""));
+    assert_eq!(k[4], (TokenKind::String, "\"x: y\""));
+}
+
+#[test]
+fn tokens_round_trip_even_when_malformed() {
+    for text in ["{\"a\": [1, 2]\n}", "{\"open: tru", "<html>ünïcode</html>", ""] {
+        let joined: String = highlight::tokens(text).iter().map(|t| t.text).collect();
+        assert_eq!(joined, text);
+    }
+}
+
+#[test]
+fn no_color_leaves_text_untouched() {
+    colored::control::set_override(false);
+    let json = "{\n  \"a\": [1, true, null]\n}";
+    assert_eq!(highlight::json(json), json);
+    assert!(!highlight::enabled());
+}
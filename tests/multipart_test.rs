@@ -0,0 +1,78 @@
+use clap::Parser;
+use rustrest::cli::Cli;
+use rustrest::client::HttpClient;
+use rustrest::config::AppConfig;
+use rustrest::request::{HttpMethod, HttpRequest, MultipartField, RequestBody};
+use std::collections::HashMap;
+
+#[test]
+fn parses_text_and_file_fields() {
+    let text = MultipartField::parse("title=Hello;world").unwrap();
+    assert_eq!(text.value.as_deref(), Some("Hello;world"));
+    assert_eq!(text.file, None);
+
+    let file = MultipartField::parse("avatar=@photo.png;type=image/png;filename=a.png").unwrap();
+    assert_eq!(
+        file,
+        MultipartField {
+            name:         "avatar".to_string(),
+            value:        None,
+            file:         Some("photo.png".to_string()),
+            content_type: Some("image/png".to_string()),
+            filename:     Some("a.png".to_string()),
+        }
+    );
+    assert_eq!(file.to_string(), "avatar=@photo.png;type=image/png;filename=a.png");
+
+    assert!(MultipartField::parse("novalue").is_err());
+    assert!(MultipartField::parse("=x").is_err());
+}
+
+#[test]
+fn cli_builds_multipart_body() {
+    let cli = Cli::try_parse_from(["rr", "post", "http://x", "-F", "a=1", "-F", "f=@file.txt"]).unwrap();
+    let req = HttpRequest::from_command(&cli.command, &AppConfig::default()).unwrap();
+    let Some(RequestBody::Multipart(fields)) = req.body else { panic!("expected multipart body") };
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[1].file.as_deref(), Some("file.txt"));
+
+    assert!(Cli::try_parse_from(["rr", "post", "http://x", "-F", "a=1", "-b", "{}"]).is_err());
+}
+
+#[tokio::test]
+async fn send_streams_file_parts() {
+    let path = std::env::temp_dir().join(format!("rustrest-multipart-{}.txt", std::process::id()));
+    std::fs::write(&path, "file contents").unwrap();
+
+    let mut server = mockito::Server::new_async().await;
+    let upload = server
+        .mock("POST", "/upload")
+        .match_header("content-type", mockito::Matcher::Regex("^multipart/form-data; boundary=".into()))
+        .match_body(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::Regex(r#"name="title"\r\n\r\nHoliday"#.into()),
+            mockito::Matcher::Regex(r#"name="doc"; filename="notes.txt"\r\nContent-Type: text/plain"#.into()),
+            mockito::Matcher::Regex("file contents".into()),
+        ]))
+        .with_status(201)
+        .create_async()
+        .await;
+
+    let body: RequestBody = serde_json::from_value(serde_json::json!({ "multipart": [
+        { "name": "title", "value": "Holiday" },
+        { "name": "doc", "file": path.to_str().unwrap(), "content_type": "text/plain", "filename": "notes.txt" }
+    ] }))
+    .unwrap();
+    let req = HttpRequest {
+        method:  HttpMethod::Post,
+        url:     format!("{}/upload", server.url()),
+        headers: HashMap::new(),
+        query:   HashMap::new(),
+        body:    Some(body),
+        auth:    None,
+    };
+    let http = HttpClient::new(&AppConfig::default()).unwrap();
+    let resp = http.send(req).await.unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+    upload.assert_async().await;
+    let _ = std::fs::remove_file(path);
+}
@@ -0,0 +1,146 @@
+use rustrest::auth::{Auth, REDACTED};
+use rustrest::client::HttpClient;
+use rustrest::config::AppConfig;
+use rustrest::oauth::{GrantType, OAuth2Config};
+use rustrest::request::{HttpMethod, HttpRequest};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+fn cache_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rustrest-oauth-{name}-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn config(server: &mockito::Server, grant_type: GrantType) -> OAuth2Config {
+    OAuth2Config {
+        token_url:     format!("{}/token", server.url()),
+        client_id:     "cli".to_string(),
+        client_secret: Some("shh".to_string()),
+        scopes:        vec!["read".to_string()],
+        grant_type,
+        username:      None,
+        password:      None,
+        refresh_token: None,
+    }
+}
+
+fn request(url: String, cfg: OAuth2Config) -> HttpRequest {
+    HttpRequest {
+        method:  HttpMethod::Get,
+        url,
+        headers: HashMap::new(),
+        query:   HashMap::new(),
+        body:    None,
+        auth:    Some(Auth::OAuth2(cfg)),
+    }
+}
+
+#[test]
+fn oauth2_deserializes_and_redacts() {
+    let auth: Auth = serde_json::from_str(
+        r#"{ "type": "oauth2", "token_url": "https://a/token", "client_id": "cli", "client_secret": "shh" }"#,
+    )
+    .unwrap();
+    let Auth::OAuth2(cfg) = auth.redacted() else { panic!("expected oauth2") };
+    assert_eq!(cfg.grant_type, GrantType::ClientCredentials);
+    assert_eq!(cfg.client_secret.as_deref(), Some(REDACTED));
+    assert_eq!(cfg.client_id, "cli");
+}
+
+#[tokio::test]
+async fn client_credentials_token_is_cached() {
+    let mut server = mockito::Server::new_async().await;
+    let token = server
+        .mock("POST", "/token")
+        .match_body(mockito::Matcher::AllOf(vec![
+            mockito::Matcher::UrlEncoded("grant_type".into(), "client_credentials".into()),
+            mockito::Matcher::UrlEncoded("scope".into(), "read".into()),
+        ]))
+        .with_body(r#"{ "access_token": "abc", "expires_in": 3600 }"#)
+        .expect(1)
+        .create_async()
+        .await;
+    let api = server
+        .mock("GET", "/me")
+        .match_header("authorization", "Bearer abc")
+        .expect(2)
+        .create_async()
+        .await;
+
+    let path = cache_path("cached");
+    let cfg  = config(&server, GrantType::ClientCredentials);
+    for _ in 0..2 {
+        let http = HttpClient::new(&AppConfig::default()).unwrap().with_token_cache(path.clone());
+        let resp = http.send(request(format!("{}/me", server.url()), cfg.clone())).await.unwrap();
+        assert_eq!(resp.status().as_u16(), 200);
+    }
+
+    token.assert_async().await;
+    api.assert_async().await;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600, "token cache must be private");
+    }
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn unauthorized_response_refreshes_token_once() {
+    let mut server = mockito::Server::new_async().await;
+    let initial = server
+        .mock("POST", "/token")
+        .match_body(mockito::Matcher::UrlEncoded("grant_type".into(), "client_credentials".into()))
+        .with_body(r#"{ "access_token": "old", "expires_in": 3600, "refresh_token": "r1" }"#)
+        .create_async()
+        .await;
+    let refreshed = server
+        .mock("POST", "/token")
+        .match_body(mockito::Matcher::UrlEncoded("refresh_token".into(), "r1".into()))
+        .with_body(r#"{ "access_token": "new", "expires_in": 3600 }"#)
+        .create_async()
+        .await;
+    let rejected = server
+        .mock("GET", "/me")
+        .match_header("authorization", "Bearer old")
+        .with_status(401)
+        .create_async()
+        .await;
+    let accepted = server
+        .mock("GET", "/me")
+        .match_header("authorization", "Bearer new")
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let path = cache_path("refresh");
+    let http = HttpClient::new(&AppConfig::default()).unwrap().with_token_cache(path.clone());
+    let cfg  = config(&server, GrantType::ClientCredentials);
+    let resp = http.send(request(format!("{}/me", server.url()), cfg)).await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    initial.assert_async().await;
+    refreshed.assert_async().await;
+    rejected.assert_async().await;
+    accepted.assert_async().await;
+    let _ = std::fs::remove_file(path);
+}
+
+#[tokio::test]
+async fn token_endpoint_errors_are_reported() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("POST", "/token")
+        .with_status(400)
+        .with_body(r#"{ "error": "invalid_client" }"#)
+        .create_async()
+        .await;
+
+    let path = cache_path("error");
+    let http = HttpClient::new(&AppConfig::default()).unwrap().with_token_cache(path);
+    let cfg  = config(&server, GrantType::ClientCredentials);
+    let err  = http.send(request(format!("{}/me", server.url()), cfg)).await.unwrap_err();
+    assert!(err.to_string().contains("invalid_client"), "{err}");
+}
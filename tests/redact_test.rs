@@ -0,0 +1,79 @@
+use rustrest::auth::{Auth, REDACTED};
+use rustrest::config::AppConfig;
+use rustrest::redact::Redaction;
+use rustrest::request::{HttpMethod, HttpRequest, RequestBody};
+use serde_json::json;
+use std::collections::HashMap;
+
+fn request(body: Option<RequestBody>) -> HttpRequest {
+    HttpRequest {
+        method:  HttpMethod::Post,
+        url:     "https://api.example.com/login".to_string(),
+        headers: [
+            ("authorization".to_string(), "Bearer abc".to_string()),
+            ("X-Request-Id".to_string(), "42".to_string()),
+        ]
+        .into(),
+        query:   HashMap::new(),
+        body,
+        auth:    Some(Auth::Bearer { token: "abc".to_string() }),
+    }
+}
+
+#[test]
+fn default_rules_mask_headers_body_fields_and_auth() {
+    let body = json!({ "user": "alice", "password": "pw", "nested": [{ "Password": "pw2" }] });
+    let safe = Redaction::default().request(&request(Some(RequestBody::Json(body))));
+
+    assert_eq!(safe.headers["authorization"], REDACTED);
+    assert_eq!(safe.headers["X-Request-Id"], "42");
+    assert_eq!(safe.auth, Some(Auth::Bearer { token: REDACTED.to_string() }));
+    let Some(RequestBody::Json(body)) = safe.body else { panic!("expected JSON body") };
+    assert_eq!(body, json!({ "user": "alice", "password": REDACTED, "nested": [{ "Password": REDACTED }] }));
+}
+
+#[test]
+fn json_paths_and_form_fields_are_masked() {
+    let rules = Redaction {
+        headers:    vec![],
+        body_paths: vec!["$.card.number".to_string(), "/ssn".to_string(), "pin".to_string()],
+    };
+    let mut value = json!({ "card": { "number": "4111", "exp": "12/30" }, "ssn": "123", "other": 1 });
+    rules.json(&mut value);
+    assert_eq!(value, json!({ "card": { "number": REDACTED, "exp": "12/30" }, "ssn": REDACTED, "other": 1 }));
+
+    let form: HashMap<_, _> = [("pin".to_string(), "1234".to_string()), ("name".to_string(), "a".to_string())].into();
+    let safe = rules.request(&request(Some(RequestBody::Form(form))));
+    let Some(RequestBody::Form(form)) = safe.body else { panic!("expected form body") };
+    assert_eq!((form["pin"].as_str(), form["name"].as_str()), (REDACTED, "a"));
+    assert_eq!(safe.headers["authorization"], "Bearer abc");
+}
+
+#[test]
+fn query_parameters_and_text_bodies_are_masked() {
+    let mut req = request(Some(RequestBody::Text(r#"{"password":"pw","note":"hi"}"#.to_string())));
+    req.url   = "https://api.example.com/items?api_key=k1&page=2".to_string();
+    req.query = [("API_KEY".to_string(), "k2".to_string()), ("page".to_string(), "2".to_string())].into();
+    let safe  = Redaction::default().request(&req);
+
+    assert_eq!(safe.url, format!("https://api.example.com/items?api_key={REDACTED}&page=2"));
+    assert_eq!((safe.query["API_KEY"].as_str(), safe.query["page"].as_str()), (REDACTED, "2"));
+    let Some(RequestBody::Text(text)) = safe.body else { panic!("expected text body") };
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&text).unwrap(), json!({ "password": REDACTED, "note": "hi" }));
+
+    assert_eq!(Redaction::default().text("plain password=pw"), "plain password=pw");
+    assert_eq!(Redaction::default().url("https://a/?q=1"), "https://a/?q=1");
+}
+
+#[test]
+fn redaction_lists_are_configurable() {
+    let mut cfg = AppConfig::default();
+    assert!(cfg.redact.is_sensitive_header("x-api-key"));
+
+    cfg.set_key("redact.headers", "X-Token, Authorization").unwrap();
+    assert_eq!(cfg.get_key("redact.headers").unwrap().as_deref(), Some("X-Token,Authorization"));
+    assert!(!cfg.redact.is_sensitive_header("Cookie"));
+
+    cfg.unset_key("redact.headers").unwrap();
+    assert!(cfg.redact.is_sensitive_header("Cookie"));
+}
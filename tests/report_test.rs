@@ -0,0 +1,120 @@
+use rustrest::assertion::AssertionFailure;
+use rustrest::auth::Auth;
+use rustrest::config::AppConfig;
+use rustrest::redact::Redaction;
+use rustrest::report::{self, json_report, junit, ReportFormat, ReportSpec};
+use rustrest::request::{HttpMethod, HttpRequest, RequestBody};
+use rustrest::response::ParsedResponse;
+use rustrest::runner::RequestOutcome;
+use serde_json::json;
+use std::path::PathBuf;
+
+fn outcome(key: &str, status: Option<u16>, error: Option<&str>, failures: Vec<AssertionFailure>) -> RequestOutcome {
+    RequestOutcome {
+        key:        key.to_string(),
+        method:     "GET".to_string(),
+        url:        format!("http://api.test/{key}"),
+        status,
+        elapsed_ms: 250,
+        error:      error.map(str::to_string),
+        failures,
+        request:    None,
+        response:   None,
+    }
+}
+
+#[test]
+fn report_spec_parses_format_and_path() {
+    let spec: ReportSpec = "junit:out/results.xml".parse().unwrap();
+    assert_eq!(spec.format, ReportFormat::Junit);
+    assert_eq!(spec.path, PathBuf::from("out/results.xml"));
+
+    assert_eq!("JSON:r.json".parse::<ReportSpec>().unwrap().format, ReportFormat::Json);
+    assert!("yaml:r.yml".parse::<ReportSpec>().is_err());
+    assert!("junit".parse::<ReportSpec>().is_err());
+    assert!("junit:".parse::<ReportSpec>().is_err());
+}
+
+#[test]
+fn junit_has_one_testcase_per_request_with_failures() {
+    let failure = AssertionFailure {
+        check:    "status".to_string(),
+        expected: "200".to_string(),
+        actual:   "500".to_string(),
+    };
+    let outcomes = vec![
+        outcome("ok", Some(200), None, vec![]),
+        outcome("broken", Some(500), None, vec![failure]),
+        outcome("down", None, Some("connection refused <x>"), vec![]),
+    ];
+
+    let xml = junit("smoke & more", &outcomes, &Redaction::default());
+    assert!(xml.contains(r#"<testsuite name="smoke &amp; more" tests="3" failures="1" errors="1""#));
+    assert!(xml.contains(r#"<testcase name="ok" classname="smoke &amp; more" time="0.250"/>"#));
+    assert!(xml.contains("status: expected 200, got 500</failure>"));
+    assert!(xml.contains(r#"<error message="connection refused &lt;x&gt;" type="error"/>"#));
+}
+
+#[test]
+fn json_report_summarises_run() {
+    let outcomes = vec![
+        outcome("ok", Some(200), None, vec![]),
+        outcome("down", None, Some("timeout"), vec![]),
+    ];
+    let report = json_report("smoke", &outcomes, &Redaction::default());
+    assert_eq!(report["summary"]["tests"], 2);
+    assert_eq!(report["summary"]["passed"], 1);
+    assert_eq!(report["summary"]["errors"], 1);
+    assert_eq!(report["requests"][1]["error"], "timeout");
+    assert!(report["requests"][0].get("response").is_none());
+}
+
+/// A failed request carrying a secret header, a Basic password and a
+/// password in the body, answered with a session cookie.
+fn secret_outcome() -> RequestOutcome {
+    let request = HttpRequest {
+        method:  HttpMethod::Post,
+        url:     "http://api.test/login?api_key=URLSECRET".to_string(),
+        headers: [("Authorization".to_string(), "Bearer SUPERSECRET".to_string())].into(),
+        query:   [("api_key".to_string(), "QUERYSECRET".to_string())].into(),
+        body:    Some(RequestBody::Json(json!({ "user": "alice", "password": "bodysecret" }))),
+        auth:    Some(Auth::Basic { username: "alice".to_string(), password: Some("hunter2".to_string()) }),
+    };
+    let response = ParsedResponse {
+        status:       500,
+        status_text:  "Internal Server Error".to_string(),
+        headers:      vec![("set-cookie".to_string(), "session=COOKIESECRET".to_string())],
+        body:         br#"{"access_token":"TOKENSECRET","user":"alice"}"#.to_vec(),
+        content_type: "application/json".to_string(),
+        size_bytes:   47,
+        elapsed_ms:   10,
+        charset:      Default::default(),
+    };
+    RequestOutcome {
+        request:  Some(request),
+        response: Some(response),
+        ..outcome("login", Some(500), Some("status 500"), vec![])
+    }
+}
+
+#[test]
+fn reports_redact_secrets() {
+    let outcomes = vec![secret_outcome()];
+    let dir = std::env::temp_dir().join(format!("rustrest-report-{}", std::process::id()));
+    let config = AppConfig::default();
+
+    for spec in [format!("junit:{}/r.xml", dir.display()), format!("json:{}/r.json", dir.display())] {
+        let spec: ReportSpec = spec.parse().unwrap();
+        report::write(&spec, "auth", &outcomes, &config).unwrap();
+        let written = std::fs::read_to_string(&spec.path).unwrap();
+
+        for secret in ["SUPERSECRET", "hunter2", "bodysecret", "COOKIESECRET", "URLSECRET", "QUERYSECRET", "TOKENSECRET"] {
+            assert!(!written.contains(secret), "{} report leaks {secret}", spec.format);
+        }
+        assert!(written.contains("alice"), "{} report should keep non-secret fields", spec.format);
+    }
+
+    let report = json_report("auth", &outcomes, &config.redact);
+    assert!(report["requests"][0]["request"].get("auth").is_none());
+    let _ = std::fs::remove_dir_all(dir);
+}
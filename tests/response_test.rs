@@ -0,0 +1,84 @@
+use rustrest::response::{self, is_binary};
+
+#[test]
+fn content_type_decides_when_known() {
+    assert!(!is_binary("application/json; charset=utf-8", b"\x00\x01"));
+    assert!(!is_binary("text/plain", b"\xff\xfe"));
+    assert!(!is_binary("application/problem+xml", b"<x/>"));
+    assert!(!is_binary("image/svg+xml", b"<svg/>"));
+    assert!(is_binary("image/png", b"anything"));
+    assert!(is_binary("application/x-protobuf", b"\x08\x96\x01"));
+    assert!(is_binary("application/gzip", b"abc"));
+}
+
+#[test]
+fn unknown_types_are_sniffed() {
+    assert!(is_binary("application/octet-stream", b"\x89PNG\r\n\x1a\n"));
+    assert!(is_binary("application/octet-stream", b"%PDF-1.7"));
+    assert!(is_binary("", b"hello\x00world"));
+    assert!(is_binary("", b"\xc3\x28 invalid utf-8"));
+    assert!(is_binary("application/x-custom", b"\x01\x02\x03\x04\x05abc"));
+    assert!(!is_binary("application/octet-stream", "plain text, naïve café\n".as_bytes()));
+    assert!(!is_binary("application/octet-stream", b""));
+}
+
+#[test]
+fn multibyte_char_cut_at_sniff_boundary_is_text() {
+    let mut body = "a".repeat(8 * 1024 - 1).into_bytes();
+    body.extend_from_slice("é and more".as_bytes());
+    assert!(!is_binary("application/octet-stream", &body));
+}
+
+#[tokio::test]
+async fn parse_response_keeps_exact_bytes() {
+    let body: Vec<u8> = (0..=255u8).collect();
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/blob")
+        .with_header("content-type", "application/octet-stream")
+        .with_body(&body)
+        .create_async()
+        .await;
+
+    let resp   = reqwest::get(format!("{}/blob", server.url())).await.unwrap();
+    let parsed = response::parse_response(resp, 1, None).await.unwrap();
+
+    assert_eq!(parsed.body, body);
+    assert_eq!(parsed.size_bytes, 256);
+    assert!(parsed.is_binary());
+}
+
+#[tokio::test]
+async fn parse_response_decodes_declared_charset() {
+    let mut server = mockito::Server::new_async().await;
+    server
+        .mock("GET", "/legacy")
+        .with_header("content-type", "text/plain; charset=windows-1252")
+        .with_body(b"na\xefve \x80")
+        .create_async()
+        .await;
+
+    let resp   = reqwest::get(format!("{}/legacy", server.url())).await.unwrap();
+    let parsed = response::parse_response(resp, 1, None).await.unwrap();
+
+    assert_eq!(parsed.text(), "naïve €");
+    assert!(!parsed.is_binary());
+}
+
+#[test]
+fn plain_output_keeps_status_line_off_stdout() {
+    let mut server = mockito::Server::new();
+    server.mock("GET", "/ping").with_body("pong").create();
+
+    let config = std::env::temp_dir().join(format!("rustrest-plain-{}", std::process::id()));
+    let out = std::process::Command::new(env!("CARGO_BIN_EXE_rr"))
+        .args(["-f", "plain", "get", &format!("{}/ping", server.url())])
+        .env("XDG_CONFIG_HOME", &config)
+        .env("NO_COLOR", "1")
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    assert_eq!(out.stdout, b"pong");
+    assert!(String::from_utf8_lossy(&out.stderr).starts_with("200 OK"));
+    let _ = std::fs::remove_dir_all(config);
+}
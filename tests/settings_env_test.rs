@@ -0,0 +1,41 @@
+//! `RR_*` variables are process-wide, so this layer is tested in its own
+//! binary to keep other tests from seeing them.
+
+use clap::Parser;
+use rustrest::cli::Cli;
+use rustrest::config::AppConfig;
+use rustrest::settings::Settings;
+
+fn resolve(args: &[&str], config: &AppConfig) -> Settings {
+    let cli = Cli::try_parse_from(["rr"].iter().chain(args).chain(&["get", "http://x"])).expect("valid arguments");
+    Settings::resolve(&cli, config)
+}
+
+#[test]
+fn environment_sits_between_config_and_cli() {
+    let config = AppConfig { timeout_secs: 12, follow_redirects: true, max_redirects: 4, ..AppConfig::default() };
+
+    std::env::set_var("RR_TIMEOUT", "7");
+    std::env::set_var("RR_MAX_REDIRECTS", "9");
+    std::env::set_var("RR_INSECURE", "1");
+    let s = resolve(&[], &config);
+    assert_eq!(s.timeout_secs, 7);
+    assert_eq!(s.max_redirects, 9);
+    assert!(s.insecure);
+
+    let s = resolve(&["--timeout", "3", "--max-redirects", "2", "--insecure=false"], &config);
+    assert_eq!(s.timeout_secs, 3);
+    assert_eq!(s.max_redirects, 2);
+    assert!(!s.insecure);
+
+    std::env::remove_var("RR_MAX_REDIRECTS");
+    std::env::set_var("RR_NO_FOLLOW", "yes");
+    assert!(!resolve(&[], &config).follow_redirects);
+    assert!(resolve(&["--no-follow=false"], &config).follow_redirects);
+
+    std::env::set_var("RR_NO_FOLLOW", "0");
+    std::env::set_var("RR_INSECURE", "false");
+    let s = resolve(&[], &config);
+    assert!(s.follow_redirects);
+    assert!(!s.insecure);
+}
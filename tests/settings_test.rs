@@ -0,0 +1,53 @@
+use clap::Parser;
+use rustrest::cli::Cli;
+use rustrest::config::AppConfig;
+use rustrest::settings::Settings;
+
+fn cli(args: &[&str]) -> Cli {
+    Cli::try_parse_from(["rr"].iter().chain(args).chain(&["get", "http://x"]))
+        .expect("valid arguments")
+}
+
+fn config() -> AppConfig {
+    AppConfig {
+        timeout_secs: 12,
+        follow_redirects: true,
+        max_redirects: 4,
+        ..AppConfig::default()
+    }
+}
+
+#[test]
+fn config_values_apply_without_flags() {
+    let s = Settings::resolve(&cli(&[]), &config());
+    assert_eq!(s.timeout_secs, 12);
+    assert!(s.follow_redirects);
+    assert!(!s.insecure);
+}
+
+#[test]
+fn cli_flags_override_config() {
+    let s = Settings::resolve(&cli(&["--timeout", "3", "--insecure"]), &config());
+    assert_eq!(s.timeout_secs, 3);
+    assert!(s.insecure);
+
+    let s = Settings::resolve(&cli(&["--no-follow"]), &config());
+    assert!(!s.follow_redirects);
+
+    let s = Settings::resolve(&cli(&["--max-redirects", "2"]), &AppConfig { follow_redirects: false, ..config() });
+    assert!(s.follow_redirects);
+    assert_eq!(s.max_redirects, 2);
+}
+
+#[test]
+fn no_follow_conflicts_with_max_redirects() {
+    let parsed = Cli::try_parse_from(["rr", "--no-follow", "--max-redirects", "3", "get", "http://x"]);
+    assert!(parsed.is_err());
+}
+
+#[test]
+fn old_config_files_get_defaults_for_new_keys() {
+    let cfg: AppConfig = serde_json::from_str(r#"{ "timeout_secs": 5 }"#).unwrap();
+    assert_eq!(cfg.timeout_secs, 5);
+    assert_eq!(cfg.max_redirects, AppConfig::default().max_redirects);
+}
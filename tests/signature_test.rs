@@ -0,0 +1,122 @@
+use chrono::{TimeZone, Utc};
+use rustrest::auth::{Auth, REDACTED};
+use rustrest::client::HttpClient;
+use rustrest::config::AppConfig;
+use rustrest::request::{HttpMethod, HttpRequest, MultipartField, RequestBody};
+use rustrest::signature::{self, HmacConfig, SignatureEncoding};
+use std::collections::HashMap;
+
+fn config(json: &str) -> HmacConfig {
+    match serde_json::from_str(json).unwrap() {
+        Auth::Hmac(cfg) => cfg,
+        other           => panic!("expected hmac, got {other:?}"),
+    }
+}
+
+#[test]
+fn defaults_apply_when_omitted() {
+    let cfg = config(r#"{ "type": "hmac", "secret": "key" }"#);
+    assert_eq!(cfg.header, "X-Signature");
+    assert_eq!(cfg.template, "{method}\n{path}\n{timestamp}\n{body}");
+    assert_eq!(cfg.encoding, SignatureEncoding::Hex);
+
+    let Auth::Hmac(redacted) = Auth::Hmac(cfg).redacted() else { unreachable!() };
+    assert_eq!(redacted.secret, REDACTED);
+}
+
+#[test]
+fn signs_default_template_over_serialized_body() {
+    let cfg = config(r#"{ "type": "hmac", "secret": "key", "timestamp_header": "X-Timestamp" }"#);
+    let mut request = reqwest::Client::new()
+        .post("https://hooks.example.com/hook")
+        .body(r#"{"a":1}"#)
+        .build()
+        .unwrap();
+    let now = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+    signature::sign(&mut request, &cfg, now).unwrap();
+
+    assert_eq!(request.headers()["x-timestamp"], "1700000000");
+    assert_eq!(
+        request.headers()["x-signature"],
+        "822e5cbbd81a5c98017cbc4e281978bccf13e876564a6ddcf69e14581841ee94"
+    );
+}
+
+#[test]
+fn custom_template_encoding_and_prefix() {
+    let cfg = config(
+        r#"{ "type": "hmac", "secret": "key", "template": "{body}", "encoding": "base64",
+             "header": "X-Hub-Signature", "prefix": "sha256=" }"#,
+    );
+    let mut request = reqwest::Client::new()
+        .post("https://hooks.example.com/")
+        .body("The quick brown fox jumps over the lazy dog")
+        .build()
+        .unwrap();
+    signature::sign(&mut request, &cfg, Utc::now()).unwrap();
+    assert_eq!(
+        request.headers()["x-hub-signature"],
+        "sha256=97yD9DBThCSxMpjmqm+xQ+9NWaFJRhdZl0edvC0aPNg="
+    );
+
+    let bad = config(r#"{ "type": "hmac", "secret": "key", "template": "{nonce}" }"#);
+    assert!(signature::sign(&mut request, &bad, Utc::now()).is_err());
+}
+
+#[tokio::test]
+async fn send_signs_json_bodies() {
+    let mut server = mockito::Server::new_async().await;
+    let hook = server
+        .mock("POST", "/hook")
+        .match_header("x-signature", mockito::Matcher::Regex("^[0-9a-f]{64}$".into()))
+        .match_header("x-timestamp", mockito::Matcher::Regex(r"^\d+$".into()))
+        .with_status(204)
+        .create_async()
+        .await;
+
+    let req = HttpRequest {
+        method:  HttpMethod::Post,
+        url:     format!("{}/hook", server.url()),
+        headers: HashMap::new(),
+        query:   HashMap::new(),
+        body:    Some(RequestBody::Json(serde_json::json!({ "event": "ping" }))),
+        auth:    Some(Auth::Hmac(config(
+            r#"{ "type": "hmac", "secret": "key", "timestamp_header": "X-Timestamp" }"#,
+        ))),
+    };
+    let http = HttpClient::new(&AppConfig::default()).unwrap();
+    let resp = http.send(req).await.unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+    hook.assert_async().await;
+}
+
+#[tokio::test]
+async fn send_signs_multipart_bodies() {
+    use hmac::{Hmac, Mac};
+
+    let mut server = mockito::Server::new_async().await;
+    let upload = server
+        .mock("POST", "/upload")
+        .match_request(|req| {
+            let mut mac = Hmac::<sha2::Sha256>::new_from_slice(b"key").unwrap();
+            mac.update(req.body().unwrap());
+            let expected: String = mac.finalize().into_bytes().iter().map(|b| format!("{b:02x}")).collect();
+            req.header("x-signature").first().is_some_and(|v| *v == expected.as_str())
+        })
+        .with_status(201)
+        .create_async()
+        .await;
+
+    let req = HttpRequest {
+        method:  HttpMethod::Post,
+        url:     format!("{}/upload", server.url()),
+        headers: HashMap::new(),
+        query:   HashMap::new(),
+        body:    Some(RequestBody::Multipart(vec![MultipartField::parse("title=hello").unwrap()])),
+        auth:    Some(Auth::Hmac(config(r#"{ "type": "hmac", "secret": "key", "template": "{body}" }"#))),
+    };
+    let http = HttpClient::new(&AppConfig::default()).unwrap();
+    let resp = http.send(req).await.unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+    upload.assert_async().await;
+}
@@ -0,0 +1,175 @@
+use chrono::{TimeZone, Utc};
+use clap::Parser;
+use rustrest::auth::Auth;
+use rustrest::cli::Cli;
+use rustrest::client::HttpClient;
+use rustrest::config::AppConfig;
+use rustrest::request::{HttpMethod, HttpRequest, MultipartField, RequestBody};
+use rustrest::sigv4::{self, AwsSigV4Config, Credentials};
+use std::collections::HashMap;
+
+fn credentials() -> Credentials {
+    Credentials {
+        access_key_id:     "AKIDEXAMPLE".to_string(),
+        secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string(),
+        session_token:     None,
+        region:            "us-east-1".to_string(),
+        service:           "service".to_string(),
+    }
+}
+
+fn signed(url: &str) -> reqwest::Request {
+    let mut request = reqwest::Client::new().get(url).build().unwrap();
+    let now = Utc.with_ymd_and_hms(2015, 8, 30, 12, 36, 0).unwrap();
+    sigv4::sign(&mut request, &credentials(), now).unwrap();
+    request
+}
+
+fn authorization(request: &reqwest::Request) -> &str {
+    request.headers()["authorization"].to_str().unwrap()
+}
+
+// Vectors from the AWS SigV4 test suite.
+
+#[test]
+fn signs_vanilla_get() {
+    let request = signed("https://example.amazonaws.com/");
+    assert_eq!(request.headers()["x-amz-date"], "20150830T123600Z");
+    assert_eq!(
+        authorization(&request),
+        "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+         SignedHeaders=host;x-amz-date, \
+         Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+    );
+}
+
+#[test]
+fn sorts_query_parameters() {
+    let request = signed("https://example.amazonaws.com/?Param2=value2&Param1=value1");
+    assert!(
+        authorization(&request)
+            .ends_with("Signature=b97d918cfa904a5beff61c982a1b6f458b799221646efd99d3219ec94cdf2500"),
+        "{}",
+        authorization(&request)
+    );
+}
+
+#[test]
+fn canonical_request_encodes_path_and_query() {
+    let request = reqwest::Client::new()
+        .get("https://example.amazonaws.com/a b/c?x=1 2&a=%2F")
+        .build()
+        .unwrap();
+    let (canonical, signed) = sigv4::canonical_request(&request, "s3", "UNSIGNED-PAYLOAD").unwrap();
+    let lines: Vec<&str> = canonical.lines().collect();
+    assert_eq!(lines[1], "/a%20b/c");
+    assert_eq!(lines[2], "a=%2F&x=1%202");
+    assert_eq!(signed, "host");
+
+    let (canonical, _) = sigv4::canonical_request(&request, "execute-api", "UNSIGNED-PAYLOAD").unwrap();
+    assert_eq!(canonical.lines().nth(1), Some("/a%2520b/c"));
+}
+
+#[test]
+fn session_token_and_s3_payload_are_signed() {
+    let mut creds = credentials();
+    creds.session_token = Some("tok".to_string());
+    creds.service       = "s3".to_string();
+    let mut request = reqwest::Client::new()
+        .put("https://bucket.s3.amazonaws.com/key")
+        .body("hello")
+        .build()
+        .unwrap();
+    sigv4::sign(&mut request, &creds, Utc::now()).unwrap();
+
+    assert_eq!(request.headers()["x-amz-security-token"], "tok");
+    assert_eq!(
+        request.headers()["x-amz-content-sha256"],
+        "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+    );
+    assert!(authorization(&request).contains("SignedHeaders=host;x-amz-content-sha256;x-amz-date;x-amz-security-token"));
+}
+
+#[test]
+fn config_parses_from_json_and_cli() {
+    let auth: Auth = serde_json::from_str(
+        r#"{ "type": "aws-sigv4", "service": "s3", "region": "eu-west-1", "access_key_id": "AK", "secret_access_key": "SK" }"#,
+    )
+    .unwrap();
+    let Auth::AwsSigV4(cfg) = auth else { panic!("expected aws-sigv4") };
+    let creds = cfg.resolve().unwrap();
+    assert_eq!((creds.access_key_id.as_str(), creds.region.as_str()), ("AK", "eu-west-1"));
+
+    let cli = Cli::try_parse_from(["rr", "--aws-sigv4", "us-east-1:execute-api", "get", "http://x"]).unwrap();
+    let Some(Auth::AwsSigV4(cfg)) = Auth::from_cli(&cli).unwrap() else { panic!("expected aws-sigv4") };
+    assert_eq!(cfg.region.as_deref(), Some("us-east-1"));
+    assert_eq!(cfg.service, "execute-api");
+
+    assert!(AwsSigV4Config::from_spec("us-east-1").is_err());
+}
+
+/// Re-sign what the server received and compare, so the test fails if the
+/// client signed anything other than the exact body bytes it sent.
+fn signature_matches(req: &mockito::Request) -> bool {
+    let header = |name: &str| req.header(name).first().and_then(|v| v.to_str().ok()).unwrap_or_default().to_string();
+    let received = header("authorization");
+    let Some(signed) = received.split("SignedHeaders=").nth(1).and_then(|s| s.split(',').next()) else { return false };
+    let now = chrono::NaiveDateTime::parse_from_str(&header("x-amz-date"), "%Y%m%dT%H%M%SZ").unwrap().and_utc();
+
+    let host = header("host");
+    let mut copy = reqwest::Client::new()
+        .request(req.method().parse().unwrap(), format!("http://{host}{}", req.path_and_query()))
+        .body(req.body().unwrap().clone());
+    for name in signed.split(';').filter(|n| !["host", "x-amz-date"].contains(n)) {
+        copy = copy.header(name, header(name));
+    }
+    let mut copy = copy.build().unwrap();
+    let mut creds = credentials();
+    creds.service = "execute-api".to_string();
+    sigv4::sign(&mut copy, &creds, now).unwrap();
+    authorization(&copy) == received
+}
+
+#[tokio::test]
+async fn send_hashes_file_and_multipart_payloads() {
+    let mut server = mockito::Server::new_async().await;
+    let api = server
+        .mock("POST", "/upload")
+        .match_request(signature_matches)
+        .with_status(200)
+        .expect(2)
+        .create_async()
+        .await;
+
+    let path = std::env::temp_dir().join(format!("rustrest-sigv4-body-{}.json", std::process::id()));
+    std::fs::write(&path, r#"{"id":1}"#).unwrap();
+    let auth = Auth::AwsSigV4(AwsSigV4Config {
+        access_key_id:     Some("AKIDEXAMPLE".to_string()),
+        secret_access_key: Some("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string()),
+        session_token:     None,
+        region:            Some("us-east-1".to_string()),
+        service:           "execute-api".to_string(),
+    });
+    let bodies = [
+        RequestBody::File(path.display().to_string()),
+        RequestBody::Multipart(vec![
+            MultipartField::parse("title=hello").unwrap(),
+            MultipartField::parse(&format!("doc=@{}", path.display())).unwrap(),
+        ]),
+    ];
+
+    let http = HttpClient::new(&AppConfig::default()).unwrap();
+    for body in bodies {
+        let req = HttpRequest {
+            method:  HttpMethod::Post,
+            url:     format!("{}/upload", server.url()),
+            headers: HashMap::new(),
+            query:   HashMap::new(),
+            body:    Some(body),
+            auth:    Some(auth.clone()),
+        };
+        assert_eq!(http.send(req).await.unwrap().status().as_u16(), 200);
+    }
+    api.assert_async().await;
+    let _ = std::fs::remove_file(path);
+}
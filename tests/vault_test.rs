@@ -0,0 +1,88 @@
+use rustrest::client::HttpClient;
+use rustrest::config::AppConfig;
+use rustrest::environment::{self, Variables};
+use rustrest::request::{HttpMethod, HttpRequest};
+use rustrest::vault::{self, Vault};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+fn vault_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rustrest-vault-{name}-{}.vault", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn secrets_round_trip_encrypted() {
+    let path = vault_path("roundtrip");
+    let mut vault = Vault::open(&path, "correct horse").unwrap();
+    vault.set("api_token", "tok-123");
+    vault.set("db", "pw");
+    vault.save().unwrap();
+
+    let raw = std::fs::read_to_string(&path).unwrap();
+    assert!(!raw.contains("tok-123"));
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600, "vault must be private");
+    }
+
+    let mut reopened = Vault::open(&path, "correct horse").unwrap();
+    assert_eq!(reopened.get("api_token"), Some("tok-123"));
+    assert_eq!(reopened.names().collect::<Vec<_>>(), vec!["api_token", "db"]);
+    assert!(reopened.remove("db"));
+    assert!(!reopened.remove("db"));
+
+    assert!(Vault::open(&path, "wrong").is_err());
+    let _ = std::fs::remove_file(path);
+}
+
+#[test]
+fn secret_references_survive_environment_expansion() {
+    let path = vault_path("expand");
+    let mut vault = Vault::open(&path, "pw").unwrap();
+    vault.set("token", "s3cret");
+
+    let vars: Variables = [("host".to_string(), "api.local".to_string())].into();
+    let expanded = environment::substitute("{{host}} {{ secret:token }}", &vars).unwrap();
+    assert_eq!(expanded, "api.local {{ secret:token }}");
+    assert_eq!(vault.expand(&expanded).unwrap(), "api.local s3cret");
+    assert!(vault.expand("{{secret:missing}}").is_err());
+}
+
+#[test]
+fn spaced_secret_references_are_detected() {
+    assert!(vault::references_secrets("Bearer {{secret:token}}"));
+    assert!(vault::references_secrets("Bearer {{ secret:token }}"));
+    assert!(vault::references_secrets("{{host}}/{{  secret:token}}"));
+    assert!(!vault::references_secrets("{{host}} secret:token"));
+}
+
+#[tokio::test]
+async fn send_resolves_secret_references() {
+    let mut server = mockito::Server::new_async().await;
+    let api = server
+        .mock("GET", "/me")
+        .match_header("x-api-key", "s3cret")
+        .with_status(200)
+        .create_async()
+        .await;
+
+    let mut vault = Vault::open(&vault_path("send"), "pw").unwrap();
+    vault.set("api_key", "s3cret");
+    let http = HttpClient::new(&AppConfig::default()).unwrap().with_vault(vault);
+
+    let req = HttpRequest {
+        method:  HttpMethod::Get,
+        url:     format!("{}/me", server.url()),
+        headers: [("X-Api-Key".to_string(), "{{secret:api_key}}".to_string())].into(),
+        query:   HashMap::new(),
+        body:    None,
+        auth:    None,
+    };
+    let resp = http.send(req).await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    api.assert_async().await;
+}